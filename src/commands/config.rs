@@ -0,0 +1,125 @@
+use std::path::{Path, PathBuf};
+
+use simple_error::{bail, SimpleError};
+
+use crate::{
+    configuration::{self, to_yaml, Configuration},
+    file_linter::{Settings, RULES},
+    module_linter::{find_module, get_root_layers, workspace::Workspace},
+};
+
+// Prints the configuration that applies to a file or directory, and the configuration files
+// each value comes from.
+pub fn show(path: &str) -> Result<(), SimpleError> {
+    let path = Path::new(path);
+    let Some(module) = find_module(path) else {
+        bail!("no go.mod file found for {}", path.display());
+    };
+
+    let mut layers = vec![(PathBuf::from("default"), Configuration::default())];
+    let workspace = Workspace::find(&module)?;
+    layers.extend(get_root_layers(
+        &module,
+        workspace.as_ref().map(|workspace| workspace.dir.as_path()),
+    )?);
+
+    let dir = if path.is_file() {
+        path.parent().unwrap()
+    } else {
+        path
+    };
+    if let (Ok(module_dir), Ok(dir)) = (module.canonicalize(), dir.canonicalize()) {
+        let mut subdir = module.clone();
+        for component in dir.strip_prefix(module_dir).unwrap().components() {
+            subdir.push(component);
+
+            let path = subdir.join(".gold.yml");
+            if path.is_file() {
+                layers.extend(Configuration::load_layers(&path)?);
+            }
+        }
+    }
+
+    println!("# Configuration for {}", path.display());
+
+    let (enable, sources) = resolve(&layers, |configuration| configuration.enable.clone());
+    let enable = enable.unwrap_or_else(|| RULES.map(String::from).to_vec());
+    print(&to_yaml("enable", &enable), &sources);
+
+    println!("\nsettings:");
+    for rule in RULES {
+        let (value, sources) = resolve(&layers, |configuration| {
+            let settings = serde_yaml::to_value(configuration.settings.as_ref()?).unwrap();
+            settings.get(rule).cloned()
+        });
+        let value = value
+            .unwrap_or_else(|| serde_yaml::to_value(Settings::defaults()).unwrap()[rule].clone());
+
+        let yaml = to_yaml(rule, &value);
+        let yaml: Vec<String> = yaml.lines().map(|line| format!("  {line}\n")).collect();
+        print(&yaml.concat(), &sources);
+    }
+
+    let (ignore, sources) = accumulate(&layers, |configuration| configuration.ignore.clone());
+    print(&to_yaml("ignore", &ignore), &sources);
+
+    let (skip, sources) = accumulate(&layers, |configuration| configuration.skip.clone());
+    print(&to_yaml("skip", &skip), &sources);
+
+    let (exclude, sources) = accumulate(&layers, |configuration| configuration.exclude.clone());
+    print(&to_yaml("exclude", &exclude), &sources);
+
+    Ok(())
+}
+
+// Finds the most specific layer that sets a value.
+fn resolve<T>(
+    layers: &[(PathBuf, Configuration)],
+    get: impl Fn(&Configuration) -> Option<T>,
+) -> (Option<T>, Vec<&Path>) {
+    for (path, configuration) in layers.iter().rev() {
+        if let Some(value) = get(configuration) {
+            return (Some(value), vec![path]);
+        }
+    }
+
+    (None, vec![Path::new("default")])
+}
+
+// Collects a list from every layer that sets it.
+fn accumulate<T>(
+    layers: &[(PathBuf, Configuration)],
+    get: impl Fn(&Configuration) -> Option<Vec<T>>,
+) -> (Vec<T>, Vec<&Path>) {
+    let mut values = vec![];
+    let mut sources = vec![];
+
+    for (path, configuration) in layers {
+        if let Some(value) = get(configuration) {
+            values.extend(value);
+            sources.push(path.as_path());
+        }
+    }
+
+    if sources.is_empty() {
+        sources.push(Path::new("default"));
+    }
+
+    (values, sources)
+}
+
+fn print(yaml: &str, sources: &[&Path]) {
+    let sources: Vec<String> = sources
+        .iter()
+        .map(|source| source.display().to_string())
+        .collect();
+
+    let indent = yaml.len() - yaml.trim_start().len();
+    println!("\n{}# from: {}", " ".repeat(indent), sources.join(", "));
+    print!("{yaml}");
+}
+
+// Prints the JSON Schema of configuration files, for editors to validate and complete them.
+pub fn schema() {
+    println!("{}", configuration::schema());
+}
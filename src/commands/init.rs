@@ -0,0 +1,40 @@
+use std::{fs, path::Path};
+
+use simple_error::{bail, SimpleError};
+
+use crate::{
+    configuration::{golangci, golangci::GolangciConfiguration, Configuration},
+    module_linter::find_module,
+};
+
+// Writes a commented .gold.yml file into the root of the module containing a path, converting
+// the module's golangci-lint configuration if it has one.
+pub fn run(path: &str) -> Result<(), SimpleError> {
+    let Some(module) = find_module(Path::new(path)) else {
+        bail!("no go.mod file found for {}", path);
+    };
+
+    let path = module.join(".gold.yml");
+    if path.exists() {
+        bail!("{} already exists", path.display());
+    }
+
+    let golangci = golangci::FILENAMES
+        .iter()
+        .map(|filename| module.join(filename))
+        .find(|path| path.is_file());
+
+    let configuration = match golangci {
+        Some(golangci) => {
+            eprintln!("Converting {}", golangci.display());
+            Configuration::from(GolangciConfiguration::load(&golangci)?)
+        }
+        None => Configuration::default(),
+    };
+
+    fs::write(&path, configuration.template())
+        .map_err(|e| SimpleError::new(format!("{}: {e}", path.display())))?;
+    eprintln!("Created {}", path.display());
+
+    Ok(())
+}
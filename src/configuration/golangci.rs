@@ -1,26 +1,215 @@
 #![allow(non_snake_case)]
 
+use std::{fs, path::Path};
+
 use serde::Deserialize;
+use simple_error::SimpleError;
+
+use super::{error, yaml_error, Pattern, Skip};
+use crate::file_linter::F002::{self, Section};
+
+// Configuration file names, in the order golangci-lint searches for them.
+pub const FILENAMES: [&str; 4] = [
+    ".golangci.yml",
+    ".golangci.yaml",
+    ".golangci.toml",
+    ".golangci.json",
+];
+
+// Linters that golangci-lint enables when no linters are configured.
+const DEFAULT_LINTERS: [&str; 6] = [
+    "errcheck",
+    "gosimple",
+    "govet",
+    "ineffassign",
+    "staticcheck",
+    "unused",
+];
+
+// golangci-lint linters and the gold rules that implement their checks.
+const LINTERS: [(&str, &str); 5] = [
+    ("gofumpt", "F001"),
+    ("gci", "F002"),
+    ("goimports", "F002"),
+    ("stylecheck", "F003"),
+    ("goimports", "F004"),
+];
 
 #[derive(Debug, Deserialize)]
 pub struct GolangciConfiguration {
     pub run: Option<Run>,
+    pub linters: Option<Linters>,
     #[serde(rename(deserialize = "linters-settings"))]
     pub linters_settings: Option<LintersSettings>,
+    pub issues: Option<Issues>,
 }
 
 #[derive(Debug, Deserialize)]
 pub struct Run {
     #[serde(rename(deserialize = "skip-dirs"))]
-    pub skip_dirs: Option<Vec<String>>,
+    pub skip_dirs: Option<Vec<Skip>>,
+    #[serde(rename(deserialize = "skip-files"))]
+    pub skip_files: Option<Vec<Pattern>>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Linters {
+    pub enable: Option<Vec<String>>,
+    pub disable: Option<Vec<String>>,
+    #[serde(rename(deserialize = "enable-all"))]
+    pub enable_all: Option<bool>,
+    #[serde(rename(deserialize = "disable-all"))]
+    pub disable_all: Option<bool>,
 }
 
 #[derive(Debug, Deserialize)]
 pub struct LintersSettings {
     pub gci: Option<Gci>,
+    pub goimports: Option<Goimports>,
 }
 
 #[derive(Debug, Deserialize)]
 pub struct Gci {
-    pub sections: Option<Vec<String>>,
+    #[serde(default, deserialize_with = "deserialize_sections")]
+    pub sections: Option<Vec<Section>>,
+    #[serde(rename(deserialize = "custom-order"))]
+    pub custom_order: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Goimports {
+    #[serde(rename(deserialize = "local-prefixes"))]
+    pub local_prefixes: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Issues {
+    #[serde(rename(deserialize = "exclude-rules"))]
+    pub exclude_rules: Option<Vec<ExcludeRule>>,
+    #[serde(rename(deserialize = "exclude-dirs"))]
+    pub exclude_dirs: Option<Vec<Skip>>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ExcludeRule {
+    pub path: Option<Pattern>,
+    #[serde(rename(deserialize = "path-except"))]
+    pub path_except: Option<Pattern>,
+    pub linters: Option<Vec<String>>,
+    pub text: Option<Pattern>,
+    pub source: Option<Pattern>,
+}
+
+fn deserialize_sections<'de, D: serde::Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<Vec<Section>>, D::Error> {
+    F002::deserialize_sections(deserializer).map(Some)
+}
+
+impl GolangciConfiguration {
+    // Reads a golangci-lint configuration file in any of the formats golangci-lint supports.
+    pub fn load(path: &Path) -> Result<Self, SimpleError> {
+        let source = fs::read_to_string(path).map_err(|e| error(path, e))?;
+
+        match path.extension().and_then(|extension| extension.to_str()) {
+            Some("toml") => toml::from_str(&source).map_err(|e| {
+                let start = e.span().map(|span| span.start).unwrap_or(0);
+                let line = source[..start].matches('\n').count() + 1;
+                let column = source[..start]
+                    .split('\n')
+                    .next_back()
+                    .unwrap()
+                    .chars()
+                    .count()
+                    + 1;
+                SimpleError::new(format!(
+                    "{}:{line}:{column}: {}",
+                    path.display(),
+                    e.message()
+                ))
+            }),
+            Some("json") => serde_json::from_str(&source).map_err(|e| {
+                SimpleError::new(format!(
+                    "{}:{}:{}: {e}",
+                    path.display(),
+                    e.line(),
+                    e.column()
+                ))
+            }),
+            _ => serde_yaml::from_str(&source).map_err(|e| yaml_error(path, e)),
+        }
+    }
+
+    // Lists the gold rules implementing the linters that golangci-lint would run.
+    pub fn enabled_rules(&self) -> Vec<String> {
+        let mut linters: Vec<&str> = vec![];
+        let mut disabled: Vec<&str> = vec![];
+
+        match &self.linters {
+            None => linters.extend(DEFAULT_LINTERS),
+            Some(configuration) => {
+                if configuration.enable_all == Some(true) {
+                    linters.extend(LINTERS.iter().map(|(linter, _)| *linter));
+                } else if configuration.disable_all != Some(true) {
+                    linters.extend(DEFAULT_LINTERS);
+                }
+
+                if let Some(enable) = &configuration.enable {
+                    linters.extend(enable.iter().map(String::as_str));
+                }
+
+                if let Some(disable) = &configuration.disable {
+                    disabled.extend(disable.iter().map(String::as_str));
+                }
+            }
+        }
+
+        linters.retain(|linter| !disabled.contains(linter));
+
+        rules(&linters)
+    }
+}
+
+// Maps golangci-lint linter names to the gold rules that implement them.
+pub fn rules(linters: &[&str]) -> Vec<String> {
+    let mut rules = vec![];
+
+    for (linter, rule) in LINTERS {
+        if linters.contains(&linter) && !rules.iter().any(|r| r == rule) {
+            rules.push(rule.to_string());
+        }
+    }
+
+    rules
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(yaml: &str) -> GolangciConfiguration {
+        serde_yaml::from_str(yaml).unwrap()
+    }
+
+    #[test]
+    fn enabled_rules() {
+        assert!(parse("run: {}").enabled_rules().is_empty());
+        assert_eq!(
+            parse("linters:\n  enable:\n    - gci\n").enabled_rules(),
+            vec![String::from("F002")]
+        );
+        assert_eq!(
+            parse("linters:\n  enable-all: true\n  disable:\n    - gci\n").enabled_rules(),
+            vec![
+                String::from("F001"),
+                String::from("F002"),
+                String::from("F003"),
+                String::from("F004")
+            ]
+        );
+        assert_eq!(
+            parse("linters:\n  disable-all: true\n  enable:\n    - gofumpt\n").enabled_rules(),
+            vec![String::from("F001")]
+        );
+    }
 }
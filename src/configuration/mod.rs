@@ -1,60 +1,817 @@
 #![allow(non_snake_case)]
 
 pub mod golangci;
+pub mod suggest;
 
-use serde::Deserialize;
+use std::{
+    collections::BTreeMap,
+    fmt,
+    fs::File,
+    marker::PhantomData,
+    path::{Component, Path, PathBuf},
+    str::FromStr,
+};
 
-use self::golangci::GolangciConfiguration;
+use regex::Regex;
+use schemars::{
+    gen::SchemaGenerator,
+    schema::{InstanceType, Schema, SchemaObject},
+    schema_for, JsonSchema,
+};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use simple_error::{bail, SimpleError};
 
-#[derive(Debug, Deserialize)]
+use self::{golangci::GolangciConfiguration, suggest::suggest};
+use crate::{
+    error::Error,
+    file_linter::{
+        Settings,
+        F002::{self, Section},
+        RULES,
+    },
+};
+
+lazy_static! {
+    static ref LOCATION_PATTERN: Regex = Regex::new(r" at line \d+ column \d+$").unwrap();
+    static ref UNKNOWN_FIELD_PATTERN: Regex =
+        Regex::new(r"unknown field `([^`]*)`, expected (.*)$").unwrap();
+}
+
+/// Gold configuration file
+#[derive(Clone, Debug, Deserialize, Serialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
 pub struct Configuration {
+    /// Configuration file to inherit from, relative to this file
+    #[serde(skip_serializing)]
+    pub extends: Option<String>,
+    /// Rules to enable, default: all rules
+    #[serde(default, deserialize_with = "deserialize_rules")]
+    #[schemars(with = "Option<Vec<Rule>>")]
     pub enable: Option<Vec<String>>,
+    /// Rule-specific settings
     pub settings: Option<Settings>,
+    /// Directories to ignore, relative to this file
     pub ignore: Option<Vec<String>>,
+    /// Patterns matching the path of directories to ignore, relative to this file
+    #[schemars(with = "Option<Vec<Pattern>>")]
+    pub skip: Option<Vec<Skip>>,
+    /// Issues to exclude
+    pub exclude: Option<Vec<Exclude>>,
+}
+
+/// Excludes issues whose file, rule, message and source line match all of the given patterns
+#[derive(Clone, Debug, Default, Deserialize, Serialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct Exclude {
+    /// Pattern matching the path of the file
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<Pattern>,
+    /// Pattern matching the path of files that are not excluded
+    #[serde(rename = "path-except", skip_serializing_if = "Option::is_none")]
+    pub path_except: Option<Pattern>,
+    /// Rules to exclude, default: all rules
+    #[serde(
+        default,
+        deserialize_with = "deserialize_rules",
+        skip_serializing_if = "Option::is_none"
+    )]
+    #[schemars(with = "Option<Vec<Rule>>")]
+    pub rules: Option<Vec<String>>,
+    /// Pattern matching the message of the issue
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text: Option<Pattern>,
+    /// Pattern matching the line of source code of the issue
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source: Option<Pattern>,
+}
+
+#[derive(Clone, Debug)]
+pub struct Pattern(pub Regex);
+
+impl FromStr for Pattern {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Regex::new(s).map(Pattern).map_err(|e| e.to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for Pattern {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserialize_str(deserializer)
+    }
+}
+
+impl Serialize for Pattern {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.0.as_str())
+    }
+}
+
+impl JsonSchema for Pattern {
+    fn schema_name() -> String {
+        String::from("Pattern")
+    }
+
+    fn json_schema(_: &mut SchemaGenerator) -> Schema {
+        SchemaObject {
+            instance_type: Some(InstanceType::String.into()),
+            format: Some(String::from("regex")),
+            ..Default::default()
+        }
+        .into()
+    }
+}
+
+// A pattern of directories to ignore, with the directory of the configuration file it is
+// relative to.
+#[derive(Clone, Debug)]
+pub struct Skip {
+    pub pattern: Pattern,
+    pub root: PathBuf,
+}
+
+impl<'de> Deserialize<'de> for Skip {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(Skip {
+            pattern: Pattern::deserialize(deserializer)?,
+            root: PathBuf::new(),
+        })
+    }
+}
+
+impl Serialize for Skip {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.pattern.serialize(serializer)
+    }
+}
+
+impl Skip {
+    // Matches the path of a directory inside the root, with "/" as separator, like golangci-lint.
+    fn matches(&self, path: &Path) -> bool {
+        let Ok(relative) = path.strip_prefix(&self.root) else {
+            return false;
+        };
+        let relative: Vec<_> = relative
+            .components()
+            .map(|component| component.as_os_str().to_string_lossy())
+            .collect();
+
+        !relative.is_empty() && self.pattern.0.is_match(&relative.join("/"))
+    }
+}
+
+impl Exclude {
+    fn applies(&self, path: &str, rule: &str) -> bool {
+        self.path
+            .as_ref()
+            .is_none_or(|path_pattern| path_pattern.0.is_match(path))
+            && !self
+                .path_except
+                .as_ref()
+                .is_some_and(|path_except| path_except.0.is_match(path))
+            && self
+                .rules
+                .as_ref()
+                .is_none_or(|rules| rules.iter().any(|r| r == rule))
+    }
+}
+
+// A rule code, validated against the rules gold implements.
+struct Rule(String);
+
+impl FromStr for Rule {
+    type Err = String;
+
+    fn from_str(code: &str) -> Result<Self, Self::Err> {
+        if RULES.contains(&code) {
+            return Ok(Rule(code.to_string()));
+        }
+
+        let mut message = format!("unknown rule `{code}`");
+        if let Some(rule) = suggest(code, &RULES) {
+            message.push_str(&format!(" (did you mean `{rule}`?)"));
+        }
+
+        Err(message)
+    }
 }
 
-#[derive(Debug, Deserialize)]
-pub struct Settings {
-    pub F002: Vec<String>,
+impl<'de> Deserialize<'de> for Rule {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserialize_str(deserializer)
+    }
+}
+
+impl JsonSchema for Rule {
+    fn schema_name() -> String {
+        String::from("Rule")
+    }
+
+    fn json_schema(_: &mut SchemaGenerator) -> Schema {
+        SchemaObject {
+            instance_type: Some(InstanceType::String.into()),
+            enum_values: Some(RULES.iter().map(|rule| (*rule).into()).collect()),
+            ..Default::default()
+        }
+        .into()
+    }
+}
+
+struct FromStrVisitor<T>(PhantomData<T>);
+
+impl<'de, T: FromStr<Err = String>> de::Visitor<'de> for FromStrVisitor<T> {
+    type Value = T;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a string")
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<T, E> {
+        v.parse().map_err(E::custom)
+    }
+}
+
+// Deserializes a value from a string with its FromStr implementation. Validation errors are
+// raised while the string is being visited, so that they are reported at its location.
+pub fn deserialize_str<'de, D: Deserializer<'de>, T: FromStr<Err = String>>(
+    deserializer: D,
+) -> Result<T, D::Error> {
+    deserializer.deserialize_str(FromStrVisitor(PhantomData))
+}
+
+fn deserialize_rules<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<Vec<String>>, D::Error> {
+    let rules = Option::<Vec<Rule>>::deserialize(deserializer)?;
+    Ok(rules.map(|rules| rules.into_iter().map(|rule| rule.0).collect()))
 }
 
 impl Configuration {
     pub fn default() -> Self {
         Configuration {
+            extends: None,
             enable: None,
-            settings: Some(Settings {
-                F002: vec![String::from("standard"), String::from("default")],
-            }),
+            settings: None,
             ignore: None,
+            skip: None,
+            exclude: None,
         }
     }
 
     pub fn from(golangci_configuration: GolangciConfiguration) -> Self {
         let mut configuration = Configuration::default();
 
-        let mut settings = configuration.settings.unwrap();
-        if let Some(linters_settings) = golangci_configuration.linters_settings {
-            if let Some(gci) = linters_settings.gci {
-                if let Some(sections) = gci.sections {
-                    settings.F002 = sections;
+        let enable = golangci_configuration.enabled_rules();
+
+        // gci and goimports only know the standard and default sections unless configured.
+        if enable.iter().any(|rule| rule == "F002") {
+            let mut settings = F002::Settings {
+                sections: vec![Section::Standard, Section::Default],
+                ..F002::Settings::default()
+            };
+
+            let linters_settings = golangci_configuration.linters_settings;
+            if let Some(gci) = linters_settings.as_ref().and_then(|l| l.gci.as_ref()) {
+                if let Some(sections) = &gci.sections {
+                    settings.sections = sections.clone();
+                }
+                settings.custom_order = gci.custom_order.unwrap_or(false);
+            } else if let Some(local_prefixes) = linters_settings
+                .and_then(|linters_settings| linters_settings.goimports)
+                .and_then(|goimports| goimports.local_prefixes)
+            {
+                // goimports puts all local imports into a single group after third-party ones.
+                let prefixes: Vec<String> = local_prefixes
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|prefix| !prefix.is_empty())
+                    .map(String::from)
+                    .collect();
+                if !prefixes.is_empty() {
+                    settings.sections.push(Section::Prefix(prefixes));
                 }
             }
+
+            configuration.settings = Some(Settings {
+                F002: Some(settings),
+                ..Default::default()
+            });
         }
-        configuration.settings = Some(settings);
+
+        configuration.enable = Some(enable);
+
+        let mut skip = vec![];
+        let mut exclude = vec![];
 
         if let Some(run) = golangci_configuration.run {
             if let Some(skip_dirs) = run.skip_dirs {
-                configuration.ignore = Some(skip_dirs);
+                skip.extend(skip_dirs);
+            }
+            if let Some(skip_files) = run.skip_files {
+                exclude.extend(skip_files.into_iter().map(|path| Exclude {
+                    path: Some(path),
+                    ..Default::default()
+                }));
+            }
+        }
+
+        if let Some(issues) = golangci_configuration.issues {
+            if let Some(exclude_dirs) = issues.exclude_dirs {
+                skip.extend(exclude_dirs);
+            }
+            if let Some(exclude_rules) = issues.exclude_rules {
+                exclude.extend(exclude_rules.into_iter().map(|rule| {
+                    let linters = rule.linters.map(|linters| {
+                        golangci::rules(&linters.iter().map(String::as_str).collect::<Vec<_>>())
+                    });
+                    Exclude {
+                        path: rule.path,
+                        path_except: rule.path_except,
+                        rules: linters,
+                        text: rule.text,
+                        source: rule.source,
+                    }
+                }));
             }
         }
 
+        if !skip.is_empty() {
+            configuration.skip = Some(skip);
+        }
+        if !exclude.is_empty() {
+            configuration.exclude = Some(exclude);
+        }
+
         configuration
     }
 
-    pub fn is_enabled(&self, rule: String) -> bool {
+    // Reads a .gold.yml file and the files it extends, from the most general to the most
+    // specific, resolving ignore patterns relative to each file.
+    pub fn load_layers(path: &Path) -> Result<Vec<(PathBuf, Self)>, SimpleError> {
+        let mut layers = vec![];
+        let mut path = normalize(path);
+
+        loop {
+            let file = File::open(&path).map_err(|e| error(&path, e))?;
+            let mut configuration: Configuration =
+                serde_yaml::from_reader(file).map_err(|e| yaml_error(&path, e))?;
+
+            let dir = path.parent().unwrap_or_else(|| Path::new("")).to_path_buf();
+            configuration = configuration.relative_to(&dir);
+
+            let extends = configuration.extends.take();
+            layers.push((path.clone(), configuration));
+
+            let Some(extends) = extends else {
+                break;
+            };
+
+            let base = normalize(&dir.join(extends));
+            if layers.iter().any(|(path, _)| *path == base) {
+                bail!("{}: circular extends of {}", path.display(), base.display());
+            }
+            path = base;
+        }
+
+        layers.reverse();
+        Ok(layers)
+    }
+
+    // Resolves ignore patterns relative to the directory of the configuration file.
+    pub fn relative_to(mut self, dir: &Path) -> Self {
+        self.ignore = self.ignore.map(|patterns| {
+            patterns
+                .iter()
+                .map(|pattern| normalize(&dir.join(pattern)).display().to_string())
+                .collect()
+        });
+        for skip in self.skip.iter_mut().flatten() {
+            skip.root = normalize(dir);
+        }
+        self
+    }
+
+    // Refines this configuration with a more specific one. Values set in the child take
+    // precedence, except for ignore patterns and exclusions, which accumulate.
+    pub fn merge(self, child: Configuration) -> Self {
+        Configuration {
+            extends: None,
+            enable: child.enable.or(self.enable),
+            settings: match (self.settings, child.settings) {
+                (Some(parent), Some(child)) => Some(parent.merge(child)),
+                (parent, child) => child.or(parent),
+            },
+            ignore: concat(self.ignore, child.ignore),
+            skip: concat(self.skip, child.skip),
+            exclude: concat(self.exclude, child.exclude),
+        }
+    }
+
+    // Renders this configuration as a commented .gold.yml file. Unset keys are commented out
+    // with an example value.
+    pub fn template(&self) -> String {
+        let mut template = String::from(
+            "# Gold configuration, see https://github.com/brianstrauch/gold#configuration\n\
+             # yaml-language-server: $schema=https://raw.githubusercontent.com/brianstrauch/gold/main/schema.json\n",
+        );
+
+        template_key(
+            &mut template,
+            "configuration file to inherit from, relative to this file, default: none",
+            "extends",
+            &self.extends,
+            "extends: ../.gold.yml",
+        );
+        template_key(
+            &mut template,
+            "rules to enable, default: all rules",
+            "enable",
+            &self.enable,
+            &format!(
+                "enable:\n{}",
+                RULES.map(|rule| format!("  - {rule}")).join("\n")
+            ),
+        );
+        template_key(
+            &mut template,
+            "rule-specific settings, default: the settings below",
+            "settings",
+            &self.settings,
+            &to_yaml("settings", &Settings::defaults()),
+        );
+        template_key(
+            &mut template,
+            "directories to ignore, relative to this file, default: []",
+            "ignore",
+            &self.ignore,
+            "ignore:\n  - mock",
+        );
+        template_key(
+            &mut template,
+            "patterns matching the path of directories to ignore, relative to this file, default: []",
+            "skip",
+            &self.skip,
+            "skip:\n  - (^|/)mocks$",
+        );
+        template_key(
+            &mut template,
+            "issues to exclude, matched by file path, rule, message and source line patterns, default: []",
+            "exclude",
+            &self.exclude,
+            "exclude:\n  - path: _test\\.go\n    rules:\n      - F001",
+        );
+
+        template
+    }
+
+    pub fn is_enabled(&self, rule: &str) -> bool {
         match &self.enable {
             None => true,
-            Some(enable) => enable.contains(&rule),
+            Some(enable) => enable.iter().any(|r| r == rule),
+        }
+    }
+
+    // Whether all issues of a rule are excluded in a file, so that the rule need not run.
+    pub fn is_excluded(&self, path: &str, rule: &str) -> bool {
+        self.exclude.iter().flatten().any(|exclude| {
+            exclude.text.is_none() && exclude.source.is_none() && exclude.applies(path, rule)
+        })
+    }
+
+    pub fn is_excluded_error(&self, error: &Error, source: &str) -> bool {
+        self.exclude.iter().flatten().any(|exclude| {
+            exclude.applies(&error.filename, &error.rule)
+                && exclude
+                    .text
+                    .as_ref()
+                    .is_none_or(|text| text.0.is_match(&error.message))
+                && exclude
+                    .source
+                    .as_ref()
+                    .is_none_or(|pattern| pattern.0.is_match(source))
+        })
+    }
+
+    pub fn is_ignored(&self, path: &Path) -> bool {
+        let path = normalize(path);
+
+        self.ignore
+            .iter()
+            .flatten()
+            .any(|ignore| *ignore == path.display().to_string())
+            || path.is_dir() && self.skip.iter().flatten().any(|skip| skip.matches(&path))
+    }
+}
+
+// Generates the JSON Schema of configuration files.
+pub fn schema() -> String {
+    serde_json::to_string_pretty(&schema_for!(Configuration)).unwrap()
+}
+
+// Serializes a single key of a configuration file.
+pub fn to_yaml<T: Serialize>(key: &str, value: &T) -> String {
+    serde_yaml::to_string(&[(key, value)].into_iter().collect::<BTreeMap<_, _>>()).unwrap()
+}
+
+fn template_key<T: Serialize>(
+    template: &mut String,
+    comment: &str,
+    key: &str,
+    value: &Option<T>,
+    example: &str,
+) {
+    template.push_str(&format!("\n# {comment}\n"));
+
+    match value {
+        Some(value) => template.push_str(&to_yaml(key, value)),
+        None => {
+            for line in example.lines() {
+                template.push_str(&format!("# {line}\n"));
+            }
+        }
+    }
+}
+
+fn concat<T>(parent: Option<Vec<T>>, child: Option<Vec<T>>) -> Option<Vec<T>> {
+    match (parent, child) {
+        (Some(mut parent), Some(child)) => {
+            parent.extend(child);
+            Some(parent)
         }
+        (parent, child) => child.or(parent),
+    }
+}
+
+// Lexically resolves "." and ".." components, so that paths reached through different
+// relative routes compare equal.
+pub fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => match normalized.components().next_back() {
+                Some(Component::Normal(_)) => {
+                    normalized.pop();
+                }
+                Some(Component::RootDir) | Some(Component::Prefix(_)) => {}
+                _ => normalized.push(".."),
+            },
+            _ => normalized.push(component),
+        }
+    }
+
+    normalized
+}
+
+fn error(path: &Path, e: impl std::fmt::Display) -> SimpleError {
+    SimpleError::new(format!("{}: {e}", path.display()))
+}
+
+// Formats a parse error as "file:line:column: message", suggesting the closest known key for
+// unknown fields.
+pub fn yaml_error(path: &Path, e: serde_yaml::Error) -> SimpleError {
+    let mut message = LOCATION_PATTERN.replace(&e.to_string(), "").to_string();
+
+    if let Some(captures) = UNKNOWN_FIELD_PATTERN.captures(&message) {
+        let fields: Vec<&str> = captures[2].split('`').skip(1).step_by(2).collect();
+        if let Some(field) = suggest(&captures[1], &fields) {
+            message.push_str(&format!(" (did you mean `{field}`?)"));
+        }
+    }
+
+    match e.location() {
+        Some(location) => SimpleError::new(format!(
+            "{}:{}:{}: {message}",
+            path.display(),
+            location.line(),
+            location.column()
+        )),
+        None => error(path, message),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn merge() {
+        let parent = Configuration {
+            extends: None,
+            enable: Some(vec![String::from("F001"), String::from("F002")]),
+            settings: Some(Settings {
+                F002: Some(F002::Settings {
+                    sections: vec![Section::Default],
+                    custom_order: true,
+                }),
+                ..Default::default()
+            }),
+            ignore: Some(vec![String::from("mock")]),
+            skip: None,
+            exclude: None,
+        };
+
+        let child = Configuration {
+            extends: None,
+            enable: Some(vec![String::from("F001")]),
+            settings: Some(Settings {
+                F001: Some(Default::default()),
+                ..Default::default()
+            }),
+            ignore: Some(vec![String::from("config/generated")]),
+            skip: None,
+            exclude: None,
+        };
+
+        let configuration = Configuration::default().merge(parent).merge(child);
+
+        assert_eq!(configuration.enable, Some(vec![String::from("F001")]));
+        let settings = configuration.settings.unwrap();
+        assert!(settings.F001.is_some());
+        assert_eq!(settings.F002.unwrap().sections, vec![Section::Default]);
+        assert_eq!(
+            configuration.ignore,
+            Some(vec![String::from("mock"), String::from("config/generated")])
+        );
+    }
+
+    #[test]
+    fn template() {
+        let configuration = Configuration::from(
+            serde_yaml::from_str("run:\n  skip-files:\n    - _test\\.go\n").unwrap(),
+        );
+        let template = configuration.template();
+
+        assert!(template.contains("\nexclude:\n- path: _test\\.go\n"));
+        assert!(template.contains("\n# ignore:\n#   - mock\n"));
+        assert!(template.contains("# yaml-language-server: $schema="));
+
+        let parsed: Configuration = serde_yaml::from_str(&template).unwrap();
+        assert_eq!(parsed.enable, configuration.enable);
+        assert!(parsed.settings.is_none());
+        assert!(
+            template.contains("\n#   F002:\n#   - standard\n#   - default\n#   - localmodule\n")
+        );
+    }
+
+    #[test]
+    fn load_extends() {
+        let layers = Configuration::load_layers(Path::new("tests/config/.gold.yml")).unwrap();
+        let paths: Vec<&Path> = layers.iter().map(|(path, _)| path.as_path()).collect();
+        assert_eq!(
+            paths,
+            vec![
+                Path::new("tests/shared.yml"),
+                Path::new("tests/config/.gold.yml")
+            ]
+        );
+
+        let configuration = layers
+            .into_iter()
+            .map(|(_, configuration)| configuration)
+            .reduce(Configuration::merge)
+            .unwrap();
+
+        assert_eq!(configuration.extends, None);
+        assert_eq!(configuration.enable, Some(vec![String::from("F001")]));
+        assert_eq!(
+            configuration.ignore,
+            Some(vec![String::from("tests/config/generated")])
+        );
+    }
+
+    fn parse(yaml: &str) -> String {
+        serde_yaml::from_str::<Configuration>(yaml)
+            .map_err(|e| yaml_error(Path::new(".gold.yml"), e))
+            .unwrap_err()
+            .to_string()
+    }
+
+    #[test]
+    fn unknown_key() {
+        assert_eq!(
+            parse("enabel:\n  - F001\n"),
+            ".gold.yml:1:1: unknown field `enabel`, expected one of `extends`, `enable`, `settings`, `ignore`, `skip`, `exclude` (did you mean `enable`?)"
+        );
+    }
+
+    #[test]
+    fn unknown_rule() {
+        assert_eq!(
+            parse("enable:\n  - F001\n  - F02\n"),
+            ".gold.yml:3:5: enable[1]: unknown rule `F02` (did you mean `F002`?)"
+        );
+        assert_eq!(
+            parse("settings:\n  F02: []\n"),
+            ".gold.yml:2:3: settings: unknown field `F02`, expected one of `F001`, `F002`, `F003`, `F004`, `F005`, `F006`, `F007`, `F008` (did you mean `F002`?)"
+        );
+    }
+
+    #[test]
+    fn malformed_section() {
+        assert_eq!(
+            parse("settings:\n  F002:\n    - standard\n    - prefx(github.com/foo)\n"),
+            ".gold.yml:4:7: settings.F002[1]: unknown section `prefx(github.com/foo)`, expected one of `standard`, `default`, `prefix(<path>,...)`, `blank`, `dot`, `alias`, `localmodule` (did you mean `prefix(github.com/foo)`?)"
+        );
+    }
+
+    #[test]
+    fn duplicate_section() {
+        assert_eq!(
+            parse("settings:\n  F002:\n    - standard\n    - default\n    - standard\n"),
+            ".gold.yml:3:5: settings.F002: duplicate section `standard`"
+        );
+    }
+
+    #[test]
+    fn from_golangci() {
+        let golangci: GolangciConfiguration = toml::from_str(
+            r#"
+            [linters]
+            enable = ["goimports"]
+
+            [linters-settings.goimports]
+            local-prefixes = "github.com/foo,github.com/bar"
+
+            [[issues.exclude-rules]]
+            path = "_test\\.go"
+            linters = ["goimports"]
+            "#,
+        )
+        .unwrap();
+
+        let configuration = Configuration::from(golangci);
+
+        assert_eq!(
+            configuration.enable,
+            Some(vec![String::from("F002"), String::from("F004")])
+        );
+        assert_eq!(
+            configuration
+                .settings
+                .clone()
+                .unwrap()
+                .F002
+                .unwrap()
+                .sections,
+            vec![
+                Section::Standard,
+                Section::Default,
+                Section::Prefix(vec![
+                    String::from("github.com/foo"),
+                    String::from("github.com/bar")
+                ])
+            ]
+        );
+        assert!(configuration.is_excluded("foo/bar_test.go", "F002"));
+        assert!(!configuration.is_excluded("foo/bar.go", "F002"));
+        assert!(!configuration.is_excluded("foo/bar_test.go", "F001"));
+    }
+
+    #[test]
+    fn from_golangci_without_settings() {
+        let golangci: GolangciConfiguration = toml::from_str(
+            r#"
+            [linters]
+            enable = ["gci"]
+            "#,
+        )
+        .unwrap();
+
+        let configuration = Configuration::from(golangci);
+
+        assert_eq!(
+            configuration.settings.unwrap().F002.unwrap().sections,
+            vec![Section::Standard, Section::Default]
+        );
+    }
+
+    #[test]
+    fn from_golangci_skip_dirs() {
+        let golangci: GolangciConfiguration =
+            serde_yaml::from_str("run:\n  skip-dirs:\n    - ^F00[12]$\n").unwrap();
+
+        let configuration = Configuration::from(golangci).relative_to(Path::new("tests"));
+
+        assert!(configuration.is_ignored(Path::new("tests/F001")));
+        assert!(configuration.is_ignored(Path::new("./tests/F002")));
+        assert!(!configuration.is_ignored(Path::new("tests/F003")));
+        assert!(!configuration.is_ignored(Path::new("tests/F001/1.go")));
+        assert!(!configuration.is_ignored(Path::new("tests")));
+    }
+
+    #[test]
+    fn normalize_path() {
+        assert_eq!(
+            normalize(Path::new("./tests/config/..")),
+            Path::new("tests")
+        );
+        assert_eq!(
+            normalize(Path::new("tests/../../shared")),
+            Path::new("../shared")
+        );
+        assert_eq!(normalize(Path::new("/../shared")), Path::new("/shared"));
     }
 }
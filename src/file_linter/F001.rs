@@ -1,3 +1,7 @@
+use std::collections::HashMap;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
 use tree_sitter::{Node, Query, QueryCursor};
 use tree_sitter_edit::{NodeId, Replace};
 
@@ -15,11 +19,37 @@ lazy_static! {
                 type: (_) @kind
             ) @decl
         ) @list
+        (type_parameter_list
+            (parameter_declaration
+                name: (identifier) @name
+                type: (_) @kind
+            ) @decl
+        ) @list
         "#
     )
     .unwrap();
 }
 
+#[derive(Clone, Debug, Default, Deserialize, Serialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+#[schemars(rename = "F001Settings")]
+pub struct Settings {
+    /// Whether parameters of the same type share it, default: grouped
+    #[serde(default)]
+    pub style: Style,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+#[schemars(rename = "F001Style")]
+pub enum Style {
+    /// Consecutive parameters of the same type share it, as in `func f(a, b int)`
+    #[default]
+    Grouped,
+    /// Every parameter has its own type, as in `func f(a int, b int)`
+    Explicit,
+}
+
 struct Parameter<'a> {
     list: Node<'a>,
     decl: Node<'a>,
@@ -28,18 +58,16 @@ struct Parameter<'a> {
 }
 
 // F001 - Redundant parameter types
-pub fn run(linter: &mut FileLinter) -> (Vec<Error>, Vec<Replace>) {
-    if !linter.configuration.is_enabled(String::from("F001")) {
-        return (vec![], vec![]);
-    }
-
+pub fn run(linter: &mut FileLinter, settings: &Settings) -> (Vec<Error>, Vec<Replace>) {
     let mut cursor = QueryCursor::new();
 
     let mut errors = vec![];
     let mut editors = vec![];
 
-    let mut last: Option<Parameter> = None;
-    let mut parameters = vec![];
+    // Parameter lists can be nested in the types of other parameters, so the parameters are
+    // grouped by list before they are compared.
+    let mut lists: Vec<Vec<Parameter>> = vec![];
+    let mut indices = HashMap::new();
 
     for m in cursor.matches(&QUERY, linter.tree.root_node(), linter.source.as_bytes()) {
         let curr = Parameter {
@@ -49,37 +77,57 @@ pub fn run(linter: &mut FileLinter) -> (Vec<Error>, Vec<Replace>) {
             kind: m.captures[3].node,
         };
 
-        if let Some(last) = last {
-            let last_kind = linter.text(last.kind);
-            let curr_kind = linter.text(curr.kind);
-
-            if last.list.id() == curr.list.id() {
-                if last.decl.id() != curr.decl.id() {
-                    if last_kind == curr_kind {
-                        if linter.fix {
-                            editors.push(Replace {
-                                id: NodeId::new(&last.decl),
-                                bytes: parameters.join(", ").as_bytes().to_vec(),
-                            });
-                        } else {
-                            errors.push(Error {
-                                filename: linter.path.clone(),
-                                position: last.kind.start_position(),
-                                rule: String::from("F001"),
-                                message: format!(r#"redundant parameter type "{}""#, last_kind),
-                            });
-                        }
+        let i = *indices.entry(curr.list.id()).or_insert_with(|| {
+            lists.push(vec![]);
+            lists.len() - 1
+        });
+        lists[i].push(curr);
+    }
+
+    for list in lists {
+        // the parameters of each declaration in the list
+        let decls: Vec<&[Parameter]> = list.chunk_by(|a, b| a.decl.id() == b.decl.id()).collect();
+
+        for (i, decl) in decls.iter().enumerate() {
+            let kind = decl[0].kind;
+            let names: Vec<&str> = decl.iter().map(|p| linter.text(p.name)).collect();
+
+            let (message, bytes) = match settings.style {
+                Style::Grouped => {
+                    let next = decls.get(i + 1).map(|next| linter.text(next[0].kind));
+                    if next != Some(linter.text(kind)) {
+                        continue;
                     }
-                    parameters = vec![];
+                    ("redundant", names.join(", "))
                 }
+                Style::Explicit => {
+                    if names.len() < 2 {
+                        continue;
+                    }
+                    let kind = linter.text(kind);
+                    let names: Vec<String> =
+                        names.iter().map(|name| format!("{name} {kind}")).collect();
+                    ("grouped", names.join(", "))
+                }
+            };
+
+            if linter.fix {
+                editors.push(Replace {
+                    id: NodeId::new(&decl[0].decl),
+                    bytes: bytes.into_bytes(),
+                });
             } else {
-                parameters = vec![];
+                errors.push(Error {
+                    filename: linter.path.clone(),
+                    position: kind.start_position(),
+                    rule: String::from("F001"),
+                    message: format!(r#"{message} parameter type "{}""#, linter.text(kind)),
+                });
             }
         }
-
-        parameters.push(linter.text(curr.name));
-        last = Some(curr);
     }
 
+    errors.sort_by_key(|error| error.position);
+
     (errors, editors)
 }
@@ -1,276 +1,749 @@
-use crate::{error::Error, file_linter::tree_sitter_go};
+use crate::{
+    configuration::{deserialize_str, suggest::suggest},
+    error::Error,
+    file_linter::tree_sitter_go,
+    module_linter::Module,
+};
 use regex::Regex;
-use std::collections::HashSet;
-use tree_sitter::{Query, QueryCursor};
+use schemars::{
+    gen::SchemaGenerator,
+    schema::{InstanceType, Metadata, Schema, SchemaObject, StringValidation, SubschemaValidation},
+    JsonSchema,
+};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use std::{
+    collections::HashMap,
+    fmt::{self, Display},
+    str::FromStr,
+};
+use tree_sitter::{Node, Query, QueryCursor};
 use tree_sitter_edit::{NodeId, Replace};
 
-use super::FileLinter;
+use super::{stdlib, FileLinter};
 
 lazy_static! {
-    static ref QUERY: Query =
-        tree_sitter::Query::new(unsafe { tree_sitter_go() }, "(import_spec_list) @list").unwrap();
-    static ref STANDARD_IMPORTS: HashSet<&'static str> = HashSet::from([
-        "archive/tar",
-        "archive/zip",
-        "arena",
-        "bufio",
-        "bytes",
-        "compress/bzip2",
-        "compress/flate",
-        "compress/gzip",
-        "compress/lzw",
-        "compress/zlib",
-        "container/heap",
-        "container/list",
-        "container/ring",
-        "context",
-        "crypto",
-        "crypto/aes",
-        "crypto/cipher",
-        "crypto/des",
-        "crypto/dsa",
-        "crypto/ecdh",
-        "crypto/ecdsa",
-        "crypto/ed25519",
-        "crypto/elliptic",
-        "crypto/hmac",
-        "crypto/md5",
-        "crypto/rand",
-        "crypto/rc4",
-        "crypto/rsa",
-        "crypto/sha1",
-        "crypto/sha256",
-        "crypto/sha512",
-        "crypto/subtle",
-        "crypto/tls",
-        "crypto/x509",
-        "crypto/x509/pkix",
-        "database/sql",
-        "database/sql/driver",
-        "debug/buildinfo",
-        "debug/dwarf",
-        "debug/elf",
-        "debug/gosym",
-        "debug/macho",
-        "debug/pe",
-        "debug/plan9obj",
-        "embed",
-        "encoding",
-        "encoding/ascii85",
-        "encoding/asn1",
-        "encoding/base32",
-        "encoding/base64",
-        "encoding/binary",
-        "encoding/csv",
-        "encoding/gob",
-        "encoding/hex",
-        "encoding/json",
-        "encoding/pem",
-        "encoding/xml",
-        "errors",
-        "expvar",
-        "flag",
-        "fmt",
-        "go/ast",
-        "go/build",
-        "go/build/constraint",
-        "go/constant",
-        "go/doc",
-        "go/doc/comment",
-        "go/format",
-        "go/importer",
-        "go/parser",
-        "go/printer",
-        "go/scanner",
-        "go/token",
-        "go/types",
-        "hash",
-        "hash/adler32",
-        "hash/crc32",
-        "hash/crc64",
-        "hash/fnv",
-        "hash/maphash",
-        "html",
-        "html/template",
-        "image",
-        "image/color",
-        "image/color/palette",
-        "image/draw",
-        "image/gif",
-        "image/jpeg",
-        "image/png",
-        "index/suffixarray",
-        "io",
-        "io/fs",
-        "io/ioutil",
-        "log",
-        "log/syslog",
-        "math",
-        "math/big",
-        "math/bits",
-        "math/cmplx",
-        "math/rand",
-        "mime",
-        "mime/multipart",
-        "mime/quotedprintable",
-        "net",
-        "net/http",
-        "net/http/cgi",
-        "net/http/cookiejar",
-        "net/http/fcgi",
-        "net/http/httptest",
-        "net/http/httptrace",
-        "net/http/httputil",
-        "net/http/pprof",
-        "net/mail",
-        "net/netip",
-        "net/rpc",
-        "net/rpc/jsonrpc",
-        "net/smtp",
-        "net/textproto",
-        "net/url",
-        "os",
-        "os/exec",
-        "os/signal",
-        "os/user",
-        "path",
-        "path/filepath",
-        "plugin",
-        "reflect",
-        "regexp",
-        "regexp/syntax",
-        "runtime",
-        "runtime/cgo",
-        "runtime/coverage",
-        "runtime/debug",
-        "runtime/metrics",
-        "runtime/pprof",
-        "runtime/race",
-        "runtime/trace",
-        "sort",
-        "strconv",
-        "strings",
-        "sync",
-        "sync/atomic",
-        "syscall",
-        "testing",
-        "testing/fstest",
-        "testing/iotest",
-        "testing/quick",
-        "text/scanner",
-        "text/tabwriter",
-        "text/template",
-        "text/template/parse",
-        "time",
-        "time/tzdata",
-        "unicode",
-        "unicode/utf16",
-        "unicode/utf8",
-        "unsafe",
-    ]);
-    static ref PREFIX_PATTERN: Regex = Regex::new(r"prefix\((.*)\)").unwrap();
+    static ref QUERY: Query = tree_sitter::Query::new(
+        unsafe { tree_sitter_go() },
+        "(import_declaration) @declaration"
+    )
+    .unwrap();
+    static ref PREFIX_PATTERN: Regex = Regex::new(r"^prefix\((.+)\)$").unwrap();
 }
 
-// F002 - Unsorted imports
-pub fn run(linter: &mut FileLinter) -> (Vec<Error>, Vec<Replace>) {
-    if !linter.configuration.is_enabled(String::from("F002")) {
-        return (vec![], vec![]);
+/// Order to sort imports by, as a list of sections or as a map with `sections` and `custom-order`
+#[derive(Clone, Debug, PartialEq)]
+pub struct Settings {
+    pub sections: Vec<Section>,
+    // keeps sections in the configured order, instead of the order of gci
+    pub custom_order: bool,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Settings {
+            sections: vec![Section::Standard, Section::Default, Section::LocalModule],
+            custom_order: true,
+        }
     }
+}
 
-    if let Some(settings) = &linter.configuration.settings {
-        let groups = &settings.F002;
-        let mut errors = vec![];
+impl Settings {
+    // Lists the sections in the order imports are sorted by.
+    pub fn ordered_sections(&self) -> Vec<Section> {
+        let mut sections = self.sections.clone();
+        if !self.custom_order {
+            sections.sort_by_key(Section::rank);
+        }
+        sections
+    }
+}
 
-        let mut sorted_imports: Vec<Vec<String>> = vec![Vec::new(); groups.len()];
-        let mut curr = 0;
+// The map form of the settings, as written in golangci-lint configuration files.
+#[derive(Deserialize, Serialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+#[schemars(rename = "F002Sections")]
+struct SettingsMap {
+    /// Sections to group imports into
+    #[serde(deserialize_with = "deserialize_sections")]
+    sections: Vec<Section>,
+    /// Keep sections in the configured order, default: false (standard, default, prefix, blank, dot, alias, localmodule)
+    #[serde(rename = "custom-order", default)]
+    custom_order: bool,
+}
 
-        let mut cursor = QueryCursor::new();
-        for m in cursor.matches(&QUERY, linter.tree.root_node(), linter.source.as_bytes()) {
-            let list = m.captures[0].node;
-            for import_spec in list.children(&mut list.walk()) {
-                let text = linter.text(import_spec);
+struct SettingsVisitor;
 
-                if text == "(" || text == ")" || text == "\n" {
-                    continue;
-                }
+impl<'de> de::Visitor<'de> for SettingsVisitor {
+    type Value = Settings;
 
-                if text == "\n\n" {
-                    curr += 1;
-                    continue;
-                }
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a list of sections or a map with `sections` and `custom-order`")
+    }
 
-                let import = text.split_whitespace().last().unwrap().trim_matches('"');
-
-                if let Some(group) = index(groups, import) {
-                    sorted_imports[group].push(format!("\t{}", text));
-                    if group < curr && errors.is_empty() {
-                        errors.push(Error {
-                            filename: linter.path.clone(),
-                            position: import_spec.start_position(),
-                            rule: String::from("F002"),
-                            message: format!(r#"unsorted import "{import}""#),
-                        });
-                    }
-                    curr = group;
-                } else if errors.is_empty() {
-                    errors.push(Error {
-                        filename: linter.path.clone(),
-                        position: import_spec.start_position(),
-                        rule: String::from("F002"),
-                        message: format!(r#"unclassified import "{import}""#),
-                    });
+    fn visit_seq<A: de::SeqAccess<'de>>(self, seq: A) -> Result<Self::Value, A::Error> {
+        Ok(Settings {
+            sections: SectionsVisitor.visit_seq(seq)?,
+            custom_order: true,
+        })
+    }
+
+    fn visit_map<A: de::MapAccess<'de>>(self, map: A) -> Result<Self::Value, A::Error> {
+        let map = SettingsMap::deserialize(de::value::MapAccessDeserializer::new(map))?;
+        Ok(Settings {
+            sections: map.sections,
+            custom_order: map.custom_order,
+        })
+    }
+}
+
+impl<'de> Deserialize<'de> for Settings {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(SettingsVisitor)
+    }
+}
+
+impl Serialize for Settings {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if self.custom_order {
+            self.sections.serialize(serializer)
+        } else {
+            SettingsMap {
+                sections: self.sections.clone(),
+                custom_order: false,
+            }
+            .serialize(serializer)
+        }
+    }
+}
+
+impl JsonSchema for Settings {
+    fn schema_name() -> String {
+        String::from("F002Settings")
+    }
+
+    fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+        SchemaObject {
+            metadata: Some(Box::new(Metadata {
+                description: Some(String::from("Order to sort imports by")),
+                ..Default::default()
+            })),
+            subschemas: Some(Box::new(SubschemaValidation {
+                any_of: Some(vec![
+                    gen.subschema_for::<Vec<Section>>(),
+                    gen.subschema_for::<SettingsMap>(),
+                ]),
+                ..Default::default()
+            })),
+            ..Default::default()
+        }
+        .into()
+    }
+}
+
+const SECTIONS: [&str; 7] = [
+    "standard",
+    "default",
+    "prefix",
+    "blank",
+    "dot",
+    "alias",
+    "localmodule",
+];
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Section {
+    Standard,
+    Default,
+    Prefix(Vec<String>),
+    Blank,
+    Dot,
+    Alias,
+    LocalModule,
+}
+
+impl Section {
+    // The position of the section in the order of gci.
+    fn rank(&self) -> usize {
+        match self {
+            Section::Standard => 0,
+            Section::Default => 1,
+            Section::Prefix(_) => 2,
+            Section::Blank => 3,
+            Section::Dot => 4,
+            Section::Alias => 5,
+            Section::LocalModule => 6,
+        }
+    }
+}
+
+impl FromStr for Section {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "standard" => return Ok(Section::Standard),
+            "default" => return Ok(Section::Default),
+            "blank" => return Ok(Section::Blank),
+            "dot" => return Ok(Section::Dot),
+            "alias" => return Ok(Section::Alias),
+            "localmodule" => return Ok(Section::LocalModule),
+            _ => {}
+        }
+
+        if let Some(captures) = PREFIX_PATTERN.captures(s) {
+            let prefixes: Vec<String> = captures[1]
+                .split(',')
+                .map(|prefix| prefix.trim().to_string())
+                .collect();
+            if prefixes.iter().all(|prefix| !prefix.is_empty()) {
+                return Ok(Section::Prefix(prefixes));
+            }
+        }
+
+        let mut message = format!(
+            "unknown section `{s}`, expected one of `standard`, `default`, `prefix(<path>,...)`, `blank`, `dot`, `alias`, `localmodule`"
+        );
+
+        let (kind, rest) = s.split_at(s.find('(').unwrap_or(s.len()));
+        if let Some(kind) = suggest(kind, &SECTIONS) {
+            let suggestion = match kind {
+                "prefix" if rest.len() > 2 && rest.ends_with(')') && !s.starts_with(kind) => {
+                    format!("{kind}{rest}")
                 }
+                "prefix" => String::from("prefix(<path>)"),
+                _ => kind.to_string(),
+            };
+            message.push_str(&format!(" (did you mean `{suggestion}`?)"));
+        }
+
+        Err(message)
+    }
+}
+
+impl Display for Section {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Section::Standard => write!(formatter, "standard"),
+            Section::Default => write!(formatter, "default"),
+            Section::Prefix(prefixes) => write!(formatter, "prefix({})", prefixes.join(",")),
+            Section::Blank => write!(formatter, "blank"),
+            Section::Dot => write!(formatter, "dot"),
+            Section::Alias => write!(formatter, "alias"),
+            Section::LocalModule => write!(formatter, "localmodule"),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Section {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserialize_str(deserializer)
+    }
+}
+
+impl Serialize for Section {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+impl JsonSchema for Section {
+    fn schema_name() -> String {
+        String::from("Section")
+    }
+
+    fn json_schema(_: &mut SchemaGenerator) -> Schema {
+        SchemaObject {
+            instance_type: Some(InstanceType::String.into()),
+            string: Some(Box::new(StringValidation {
+                pattern: Some(String::from(
+                    r"^(standard|default|blank|dot|alias|localmodule|prefix\([^,]+(,[^,]+)*\))$",
+                )),
+                ..Default::default()
+            })),
+            ..Default::default()
+        }
+        .into()
+    }
+}
+
+struct SectionsVisitor;
+
+impl<'de> de::Visitor<'de> for SectionsVisitor {
+    type Value = Vec<Section>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a list of sections")
+    }
+
+    fn visit_seq<A: de::SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut sections = vec![];
+
+        while let Some(section) = seq.next_element::<Section>()? {
+            if sections.contains(&section) {
+                return Err(de::Error::custom(format!("duplicate section `{section}`")));
             }
+            sections.push(section);
+        }
 
-            let mut editors = vec![];
+        Ok(sections)
+    }
+}
+
+// Deserializes a list of sections, rejecting sections that are listed more than once.
+pub fn deserialize_sections<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Vec<Section>, D::Error> {
+    deserializer.deserialize_seq(SectionsVisitor)
+}
+
+// An import, as classified into sections.
+pub struct Import<'a> {
+    pub name: Option<&'a str>,
+    pub path: &'a str,
+}
 
-            if !errors.is_empty() {
-                let sections: Vec<String> = sorted_imports
-                    .iter()
-                    .filter(|v| !v.is_empty())
-                    .map(|v| v.join("\n"))
-                    .collect();
-                let out = format!("(\n{}\n)", sections.join("\n\n"));
+// An import spec of an import list, with the comments attached to it: comments on the lines
+// above it and a comment at the end of its line move with the import when it is sorted.
+pub struct Spec<'a> {
+    pub node: Node<'a>,
+    pub import: Import<'a>,
+    // the byte range of the spec with its comments
+    pub start: usize,
+    pub end: usize,
+    // the index of the group of specs, separated by blank lines, that the spec is in
+    block: usize,
+}
+
+// F002 - Unsorted imports
+pub fn run(linter: &mut FileLinter, settings: &Settings) -> (Vec<Error>, Vec<Replace>) {
+    let groups = settings.ordered_sections();
+    let newline = if linter.source.contains("\r\n") {
+        "\r\n"
+    } else {
+        "\n"
+    };
 
-                editors.push(Replace {
-                    id: NodeId::new(&list),
-                    bytes: out.as_bytes().to_vec(),
-                });
+    // All import declarations of a file are sorted as one, except for cgo's import "C", which has
+    // to stay next to its preamble.
+    let mut declarations = vec![];
+    let mut specs = vec![];
+    let mut comments = vec![];
+
+    for (declaration, declaration_specs, declaration_comments) in self::declarations(linter) {
+        if declaration_specs.iter().any(|spec| spec.import.path == "C") {
+            continue;
+        }
+
+        declarations.push(declaration);
+        specs.extend(declaration_specs);
+        comments.extend(declaration_comments);
+    }
+
+    let mut errors = vec![];
+    // imports grouped by section, followed by the imports no section matches
+    let mut sorted_imports: Vec<Vec<&Spec>> = vec![Vec::new(); groups.len() + 1];
+    // the block, group and path of the previous import that is in the right section
+    let mut previous: Option<(usize, usize, &str)> = None;
+
+    for declaration in declarations.iter().skip(1) {
+        errors.push(Error {
+            filename: linter.path.clone(),
+            position: declaration.start_position(),
+            rule: String::from("F002"),
+            message: String::from("multiple import declarations"),
+        });
+    }
+
+    for spec in &specs {
+        let path = spec.import.path;
+
+        let Some(group) = index(&groups, &spec.import, linter.module) else {
+            sorted_imports[groups.len()].push(spec);
+            errors.push(Error {
+                filename: linter.path.clone(),
+                position: spec.node.start_position(),
+                rule: String::from("F002"),
+                message: format!(r#"unclassified import "{path}", no section matches it"#),
+            });
+            continue;
+        };
+        sorted_imports[group].push(spec);
 
-                return (errors, editors);
+        let message = match previous {
+            // Sections are separated by blank lines, in the configured order.
+            Some((block, previous_group, _))
+                if group < previous_group || (block == spec.block) != (previous_group == group) =>
+            {
+                Some(format!(
+                    r#"unsorted import "{path}", expected in the {} section"#,
+                    groups[group]
+                ))
             }
+            Some((_, previous_group, before)) if previous_group == group && path < before => Some(
+                format!(r#"unsorted import "{path}", expected before "{before}""#),
+            ),
+            _ => None,
+        };
+
+        let misplaced = previous.is_some_and(|(_, previous_group, _)| group < previous_group);
+        if !misplaced {
+            previous = Some((spec.block, group, path));
+        }
+
+        if let Some(message) = message {
+            errors.push(Error {
+                filename: linter.path.clone(),
+                position: spec.node.start_position(),
+                rule: String::from("F002"),
+                message,
+            });
+        }
+    }
+
+    if errors.is_empty() {
+        return (errors, vec![]);
+    }
+
+    // The comments above the declarations that are removed move with their first import, and a
+    // comment after a declaration of a single import moves with the import.
+    let mut attached: HashMap<usize, (Vec<Node>, Option<Node>)> = HashMap::new();
+    for (i, declaration) in declarations.iter().enumerate() {
+        let Some(spec) = specs
+            .iter()
+            .find(|spec| declaration.byte_range().contains(&spec.node.start_byte()))
+        else {
+            continue;
+        };
+
+        let leading = if i > 0 {
+            leading_comments(*declaration)
+        } else {
+            vec![]
+        };
+        let trailing = declaration
+            .next_named_sibling()
+            .filter(|_| spec.node.parent() == Some(*declaration))
+            .filter(|node| node.kind() == "comment")
+            .filter(|node| node.start_position().row == declaration.end_position().row);
+        attached.insert(spec.node.id(), (leading, trailing));
+    }
+
+    let mut sections: Vec<String> = sorted_imports
+        .iter_mut()
+        .filter(|v| !v.is_empty())
+        .map(|v| {
+            v.sort_by_key(|spec| spec.import.path);
+            let lines: Vec<String> = v
+                .iter()
+                .map(|spec| {
+                    let mut line = String::from("\t");
+                    let (leading, trailing) = attached
+                        .get(&spec.node.id())
+                        .map_or((&[][..], None), |(leading, trailing)| (leading, *trailing));
+                    for comment in leading {
+                        line.push_str(&format!("{}{newline}\t", linter.text(*comment)));
+                    }
+                    line.push_str(spec.text(&linter.source));
+                    if let Some(comment) = trailing {
+                        line.push_str(&format!(" {}", linter.text(comment)));
+                    }
+                    line
+                })
+                .collect();
+            lines.join(newline)
+        })
+        .collect();
+    if let Some(section) = sections.last_mut() {
+        for comment in comments {
+            section.push_str(&format!("{newline}\t{comment}"));
         }
     }
+    let out = format!(
+        "import ({newline}{}{newline})",
+        sections.join(&newline.repeat(2))
+    );
 
-    (vec![], vec![])
+    // The imports are merged into the first declaration, and the other ones are removed.
+    let editors = declarations
+        .iter()
+        .enumerate()
+        .map(|(i, declaration)| Replace {
+            id: NodeId::new(declaration),
+            bytes: if i == 0 {
+                out.as_bytes().to_vec()
+            } else {
+                vec![]
+            },
+        })
+        .chain(
+            attached
+                .values()
+                .flat_map(|(leading, trailing)| leading.iter().chain(trailing))
+                .map(|comment| Replace {
+                    id: NodeId::new(comment),
+                    bytes: vec![],
+                }),
+        )
+        .collect();
+
+    (errors, editors)
 }
 
-fn index(groups: &[String], import: &str) -> Option<usize> {
-    let mut default_group = None;
-    let mut prefix_group = None;
+// Lists the import declarations of a file with their specs and the comments after their last
+// spec. Blocks of specs are numbered across declarations.
+pub fn declarations<'a>(linter: &'a FileLinter) -> Vec<(Node<'a>, Vec<Spec<'a>>, Vec<&'a str>)> {
+    let mut declarations: Vec<(Node, Vec<Spec>, Vec<&str>)> = vec![];
+
+    let mut cursor = QueryCursor::new();
+    for m in cursor.matches(&QUERY, linter.tree.root_node(), linter.source.as_bytes()) {
+        let declaration = m.captures[0].node;
+        let container = declaration
+            .named_children(&mut declaration.walk())
+            .find(|child| child.kind() == "import_spec_list")
+            .unwrap_or(declaration);
 
-    let mut longest_prefix = 0;
+        let block = declarations
+            .iter()
+            .flat_map(|(_, specs, _)| specs.last())
+            .next_back()
+            .map_or(0, |spec| spec.block + 1);
+        let (specs, comments) = specs(linter, container, block);
+
+        declarations.push((declaration, specs, comments));
+    }
 
-    for (i, rule) in groups.iter().enumerate() {
-        if rule == "standard" && STANDARD_IMPORTS.contains(import) {
-            return Some(i);
+    declarations
+}
+
+// Lists the comments on the lines right above a declaration, other than a comment at the end of
+// the line of the node before them.
+fn leading_comments(declaration: Node) -> Vec<Node> {
+    let mut comments = vec![];
+    let mut next = declaration;
+    while let Some(comment) = next
+        .prev_named_sibling()
+        .filter(|node| node.kind() == "comment")
+        .filter(|node| node.end_position().row + 1 == next.start_position().row)
+    {
+        let trailing = comment
+            .prev_named_sibling()
+            .is_some_and(|node| node.end_position().row == comment.start_position().row);
+        if trailing {
+            break;
+        }
+        comments.insert(0, comment);
+        next = comment;
+    }
+    comments
+}
+
+// Lists the import specs of an import declaration or import list with their comments, and the
+// comments after the last import. Blank lines separate blocks of specs, numbered from `block`.
+fn specs<'a>(
+    linter: &'a FileLinter,
+    container: Node<'a>,
+    mut block: usize,
+) -> (Vec<Spec<'a>>, Vec<&'a str>) {
+    let source = linter.source.as_str();
+
+    let mut specs: Vec<Spec> = vec![];
+    // the end row of the previous spec, including its trailing comment
+    let mut end_row = None;
+    // the start of the comments above the next spec
+    let mut leading: Option<Node> = None;
+
+    for child in container.named_children(&mut container.walk()) {
+        let start = leading.unwrap_or(child);
+
+        if child.kind() == "comment" {
+            let trailing = leading.is_none() && end_row == Some(child.start_position().row);
+            match specs.last_mut() {
+                Some(spec) if trailing => spec.end = child.end_byte(),
+                _ => leading = Some(start),
+            }
+            continue;
         }
 
-        if rule == "default" {
-            default_group = Some(i);
+        if child.kind() != "import_spec" {
             continue;
         }
 
-        if let Some(captures) = PREFIX_PATTERN.captures(rule) {
-            if let Some(prefix) = captures.get(1) {
-                if import.starts_with(prefix.as_str()) && prefix.len() > longest_prefix {
-                    prefix_group = Some(i);
-                    longest_prefix = prefix.len();
-                    continue;
+        if end_row.is_some_and(|row| start.start_position().row > row + 1) {
+            block += 1;
+        }
+        leading = None;
+        end_row = Some(child.end_position().row);
+
+        specs.push(Spec {
+            node: child,
+            import: Import {
+                name: child
+                    .child_by_field_name("name")
+                    .map(|name| linter.text(name)),
+                path: child
+                    .child_by_field_name("path")
+                    .map_or("", |path| linter.text(path))
+                    .trim_matches(|c| c == '"' || c == '`'),
+            },
+            start: start.start_byte(),
+            end: child.end_byte(),
+            block,
+        });
+    }
+
+    let comments = match leading {
+        Some(start) => source[start.start_byte()..container.end_byte()]
+            .trim_end_matches(')')
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .collect(),
+        None => vec![],
+    };
+
+    (specs, comments)
+}
+
+impl Spec<'_> {
+    // The text of the spec with its comments, without the carriage return that line comments end
+    // with in files with CRLF line endings.
+    fn text<'s>(&self, source: &'s str) -> &'s str {
+        source[self.start..self.end].trim_end_matches('\r')
+    }
+}
+
+// Finds the section of an import. Like gci, the most specific matching section wins: sections
+// matching the import name come first, then prefixes by length, the standard library and finally
+// the default section.
+fn index(groups: &[Section], import: &Import, module: &Module) -> Option<usize> {
+    let mut best = None;
+    let mut best_specificity = (0, 0);
+
+    for (i, section) in groups.iter().enumerate() {
+        let specificity = match section {
+            Section::Default => (1, 0),
+            Section::Standard if stdlib::is_standard(import.path, module) => (2, 0),
+            Section::Prefix(prefixes) => prefixes
+                .iter()
+                .filter(|prefix| import.path.starts_with(prefix.as_str()))
+                .map(|prefix| (3, prefix.len()))
+                .max()
+                .unwrap_or((0, 0)),
+            Section::LocalModule => match &module.path {
+                Some(module)
+                    if import.path == module || import.path.starts_with(&format!("{module}/")) =>
+                {
+                    (3, module.len())
                 }
-            }
+                _ => (0, 0),
+            },
+            Section::Blank if import.name == Some("_") => (4, 0),
+            Section::Dot if import.name == Some(".") => (4, 0),
+            Section::Alias if import.name.is_some_and(|name| name != "_" && name != ".") => (4, 0),
+            _ => (0, 0),
+        };
+
+        if specificity > best_specificity {
+            best = Some(i);
+            best_specificity = specificity;
         }
     }
 
-    prefix_group.or(default_group)
+    best
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_section() {
+        assert_eq!("standard".parse(), Ok(Section::Standard));
+        assert_eq!("localmodule".parse(), Ok(Section::LocalModule));
+        assert_eq!(
+            "prefix(github.com/foo, github.com/bar)".parse(),
+            Ok(Section::Prefix(vec![
+                String::from("github.com/foo"),
+                String::from("github.com/bar")
+            ]))
+        );
+        assert_eq!(
+            "prefx(github.com/foo)".parse::<Section>(),
+            Err(String::from("unknown section `prefx(github.com/foo)`, expected one of `standard`, `default`, `prefix(<path>,...)`, `blank`, `dot`, `alias`, `localmodule` (did you mean `prefix(github.com/foo)`?)"))
+        );
+        assert_eq!(
+            "prefix()".parse::<Section>(),
+            Err(String::from("unknown section `prefix()`, expected one of `standard`, `default`, `prefix(<path>,...)`, `blank`, `dot`, `alias`, `localmodule` (did you mean `prefix(<path>)`?)"))
+        );
+        assert_eq!(
+            "blnk".parse::<Section>(),
+            Err(String::from("unknown section `blnk`, expected one of `standard`, `default`, `prefix(<path>,...)`, `blank`, `dot`, `alias`, `localmodule` (did you mean `blank`?)"))
+        );
+    }
+
+    #[test]
+    fn parse_settings() {
+        let settings: Settings = serde_yaml::from_str("[default, standard]").unwrap();
+        assert_eq!(
+            settings.ordered_sections(),
+            vec![Section::Default, Section::Standard]
+        );
+
+        let settings: Settings =
+            serde_yaml::from_str("sections: [localmodule, dot, default, standard]").unwrap();
+        assert_eq!(
+            settings.ordered_sections(),
+            vec![
+                Section::Standard,
+                Section::Default,
+                Section::Dot,
+                Section::LocalModule
+            ]
+        );
+        assert_eq!(
+            serde_yaml::to_string(&settings).unwrap(),
+            "sections:\n- localmodule\n- dot\n- default\n- standard\ncustom-order: false\n"
+        );
+
+        assert!(serde_yaml::from_str::<Settings>("sections: [default]\norder: true").is_err());
+    }
+
+    #[test]
+    fn classify() {
+        let groups = [
+            Section::Standard,
+            Section::Default,
+            Section::Prefix(vec![
+                String::from("github.com/a"),
+                String::from("github.com/b"),
+            ]),
+            Section::Prefix(vec![String::from("github.com/a/c")]),
+            Section::Blank,
+            Section::Dot,
+            Section::Alias,
+            Section::LocalModule,
+        ];
+        let module = Module {
+            path: Some(String::from("example.com/m")),
+            go_version: None,
+            dir: None,
+        };
+        let index = |name, path| index(&groups, &Import { name, path }, &module);
+
+        assert_eq!(index(None, "fmt"), Some(0));
+        assert_eq!(index(None, "example.com/x"), Some(1));
+        assert_eq!(index(None, "github.com/b/x"), Some(2));
+        assert_eq!(index(None, "github.com/a/c/x"), Some(3));
+        assert_eq!(index(Some("_"), "fmt"), Some(4));
+        assert_eq!(index(Some("."), "github.com/a"), Some(5));
+        assert_eq!(index(Some("x"), "example.com/m/x"), Some(6));
+        assert_eq!(index(None, "example.com/m/x"), Some(7));
+        assert_eq!(index(None, "example.com/mx"), Some(1));
+
+        let groups = Settings::default().ordered_sections();
+        let default = |path| super::index(&groups, &Import { name: None, path }, &module);
+        assert_eq!(default("fmt"), Some(0));
+        assert_eq!(default("example.com/x"), Some(1));
+        assert_eq!(default("example.com/m/x"), Some(2));
+    }
 }
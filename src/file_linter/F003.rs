@@ -0,0 +1,112 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use tree_sitter_edit::{NodeId, Replace};
+
+use crate::error::Error;
+
+use super::{FileLinter, F002};
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+#[schemars(rename = "F003Settings")]
+pub struct Settings {}
+
+// F003 - Duplicate imports
+pub fn run(linter: &mut FileLinter, _settings: &Settings) -> (Vec<Error>, Vec<Replace>) {
+    let mut errors = vec![];
+    let mut editors = vec![];
+
+    let declarations = F002::declarations(linter);
+    let specs: Vec<&F002::Spec> = declarations
+        .iter()
+        .flat_map(|(_, specs, _)| specs)
+        .collect();
+
+    // the imports that are kept of each path
+    let mut kept: Vec<&F002::Spec> = vec![];
+
+    for spec in &specs {
+        let F002::Import { name, path } = spec.import;
+
+        if let Some(first) = kept.iter_mut().find(|s| s.import.path == path) {
+            errors.push(Error {
+                filename: linter.path.clone(),
+                position: spec.node.start_position(),
+                rule: String::from("F003"),
+                message: format!(r#"duplicate import "{path}""#),
+            });
+
+            // The duplicate can only be removed if no code refers to it by a name of its own. A
+            // blank import comes second to one that is used, so it is removed in its place.
+            if name == first.import.name || name == Some("_") {
+                editors.push(remove(spec));
+            } else if first.import.name == Some("_") {
+                editors.push(remove(first));
+                *first = spec;
+            }
+            continue;
+        }
+        kept.push(spec);
+
+        let Some(alias) = name.filter(|name| is_redundant(name, path)) else {
+            continue;
+        };
+
+        errors.push(Error {
+            filename: linter.path.clone(),
+            position: spec.node.start_position(),
+            rule: String::from("F003"),
+            message: format!(r#"redundant alias "{alias}" for import "{path}""#),
+        });
+
+        // The alias is dropped by keeping only the path of the spec.
+        if let Some(package) = spec.node.child_by_field_name("path") {
+            editors.push(Replace {
+                id: NodeId::new(&spec.node),
+                bytes: linter.text(package).as_bytes().to_vec(),
+            });
+        }
+    }
+
+    (errors, editors)
+}
+
+// Removes an import spec. An import that is alone in its declaration is removed with the
+// declaration.
+fn remove(spec: &F002::Spec) -> Replace {
+    let node = spec
+        .node
+        .parent()
+        .filter(|parent| parent.kind() == "import_declaration")
+        .unwrap_or(spec.node);
+    Replace {
+        id: NodeId::new(&node),
+        bytes: vec![],
+    }
+}
+
+// Checks whether an alias names an import like its path does. A major version suffix, as in
+// "example.com/foo/v2", is not the name of the package, so an alias of it is not redundant.
+fn is_redundant(alias: &str, path: &str) -> bool {
+    let last = path.rsplit('/').next().unwrap();
+    let is_version = path.contains('/')
+        && last
+            .strip_prefix('v')
+            .is_some_and(|n| !n.is_empty() && n.chars().all(|c| c.is_ascii_digit()));
+
+    alias == last && !is_version
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn redundant_alias() {
+        assert!(is_redundant("fmt", "fmt"));
+        assert!(is_redundant("pflag", "github.com/spf13/pflag"));
+        assert!(!is_redundant("flag", "github.com/spf13/pflag"));
+        assert!(!is_redundant("v2", "example.com/foo/v2"));
+        assert!(!is_redundant("_", "embed"));
+    }
+}
@@ -0,0 +1,258 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fs,
+    ops::Range,
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use tree_sitter::{Query, QueryCursor};
+use tree_sitter_edit::{NodeId, Replace};
+
+use crate::{error::Error, file_linter::tree_sitter_go, module_linter::Module};
+
+use super::{parse, stdlib, FileLinter, F002};
+
+lazy_static! {
+    static ref QUERY: Query = tree_sitter::Query::new(
+        unsafe { tree_sitter_go() },
+        r#"
+        (selector_expression operand: (identifier) @package)
+        (qualified_type package: (package_identifier) @package)
+        "#
+    )
+    .unwrap();
+    static ref PACKAGE_QUERY: Query = tree_sitter::Query::new(
+        unsafe { tree_sitter_go() },
+        "(package_clause (package_identifier) @name)"
+    )
+    .unwrap();
+    // package names of the directories in modules, by directory
+    static ref PACKAGES: Mutex<HashMap<PathBuf, Option<String>>> = Mutex::new(HashMap::new());
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+#[schemars(rename = "F004Settings")]
+pub struct Settings {}
+
+// F004 - Unused imports
+pub fn run(linter: &mut FileLinter, _settings: &Settings) -> (Vec<Error>, Vec<Replace>) {
+    let mut errors = vec![];
+    let mut editors = vec![];
+
+    let mut used = HashSet::new();
+    let mut cursor = QueryCursor::new();
+    for m in cursor.matches(&QUERY, linter.tree.root_node(), linter.source.as_bytes()) {
+        used.insert(linter.text(m.captures[0].node));
+    }
+
+    for (declaration, specs, comments) in F002::declarations(linter) {
+        // Blank and dot imports are not referred to by name, and cgo's import "C" is special. An
+        // import whose package name is only assumed is used if it is referred to by that name or
+        // by the last element of its path, and it is not removed, since the name can be wrong.
+        let mut unused: Vec<&F002::Spec> = vec![];
+        let mut removable: Vec<&F002::Spec> = vec![];
+        for spec in specs.iter().filter(|spec| spec.import.path != "C") {
+            let (is_used, is_known) = match spec.import.name {
+                Some("_" | ".") => continue,
+                Some(name) => (used.contains(name), true),
+                None => match package_name(spec.import.path, linter.module) {
+                    Some(name) => (used.contains(name.as_str()), true),
+                    None => {
+                        let last = spec.import.path.rsplit('/').next().unwrap();
+                        let assumed = assumed_name(spec.import.path);
+                        (
+                            used.contains(assumed.as_str()) || used.contains(last),
+                            false,
+                        )
+                    }
+                },
+            };
+
+            if !is_used {
+                unused.push(spec);
+                if is_known {
+                    removable.push(spec);
+                }
+            }
+        }
+
+        for spec in &unused {
+            errors.push(Error {
+                filename: linter.path.clone(),
+                position: spec.node.start_position(),
+                rule: String::from("F004"),
+                message: format!(r#"unused import "{}""#, spec.import.path),
+            });
+        }
+
+        if removable.is_empty() {
+            continue;
+        }
+
+        let bytes = if removable.len() == specs.len() && comments.is_empty() {
+            vec![]
+        } else {
+            remove_specs(&linter.source, declaration.byte_range(), &removable).into_bytes()
+        };
+        editors.push(Replace {
+            id: NodeId::new(&declaration),
+            bytes,
+        });
+    }
+
+    (errors, editors)
+}
+
+// Removes the lines of import specs from an import declaration, along with the blank lines that
+// would be left at the start or end of the list or next to another blank line.
+fn remove_specs(source: &str, range: Range<usize>, specs: &[&F002::Spec]) -> String {
+    let line_start = |i: usize| source[..i].rfind('\n').map_or(0, |i| i + 1);
+    let line_end = |i: usize| source[i..].find('\n').map_or(source.len(), |j| i + j + 1);
+
+    let mut text = String::new();
+    let mut start = range.start;
+    for spec in specs {
+        text.push_str(&source[start..line_start(spec.start)]);
+        start = line_end(spec.end).min(range.end);
+    }
+    text.push_str(&source[start..range.end]);
+
+    let mut lines: Vec<&str> = vec![];
+    let is_blank = |line: &str| line.trim().is_empty();
+    for line in text.split_inclusive('\n') {
+        let after_blank = lines
+            .last()
+            .is_none_or(|last| is_blank(last) || last.trim_end().ends_with('('));
+        if is_blank(line) && after_blank {
+            continue;
+        }
+        if line.trim_start().starts_with(')') && lines.last().is_some_and(|last| is_blank(last)) {
+            lines.pop();
+        }
+        lines.push(line);
+    }
+
+    lines.concat()
+}
+
+// Finds the name of the package at an import path, if it is known: a package of the module being
+// linted is read from its source, and a package of the standard library is named after its path.
+fn package_name(path: &str, module: &Module) -> Option<String> {
+    let dir = module
+        .path
+        .as_ref()
+        .zip(module.dir.as_ref())
+        .and_then(|(module, dir)| {
+            let rest = path.strip_prefix(module.as_str())?;
+            (rest.is_empty() || rest.starts_with('/'))
+                .then(|| dir.join(rest.trim_start_matches('/')))
+        });
+
+    match dir {
+        Some(dir) => read_package_name(&dir),
+        None => stdlib::is_standard(path, module).then(|| assumed_name(path)),
+    }
+}
+
+// Names a package after its import path, like goimports does: after the last element of the path
+// without a major version, a "go-" prefix or anything after a character that is not allowed in
+// names.
+fn assumed_name(path: &str) -> String {
+    let mut elements = path.rsplit('/');
+    let mut name = elements.next().unwrap();
+
+    let is_version = name
+        .strip_prefix('v')
+        .is_some_and(|n| !n.is_empty() && n.chars().all(|c| c.is_ascii_digit()));
+    if is_version {
+        name = elements.next().unwrap_or(name);
+    }
+
+    let name = name.strip_prefix("go-").unwrap_or(name);
+    let end = name
+        .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+        .unwrap_or(name.len());
+
+    name[..end].to_string()
+}
+
+// Reads the package clause of the first non-test Go file in a directory.
+fn read_package_name(dir: &Path) -> Option<String> {
+    let mut packages = PACKAGES.lock().unwrap();
+    if let Some(name) = packages.get(dir) {
+        return name.clone();
+    }
+
+    let mut files: Vec<PathBuf> = fs::read_dir(dir)
+        .ok()?
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|path| {
+            let name = path.file_name().unwrap().to_string_lossy();
+            name.ends_with(".go") && !name.ends_with("_test.go")
+        })
+        .collect();
+    files.sort();
+
+    let name = files.iter().find_map(|file| {
+        let source = fs::read_to_string(file).ok()?;
+        let tree = parse(&source);
+        let mut cursor = QueryCursor::new();
+        let m = cursor
+            .matches(&PACKAGE_QUERY, tree.root_node(), source.as_bytes())
+            .next()?;
+        Some(
+            m.captures[0]
+                .node
+                .utf8_text(source.as_bytes())
+                .ok()?
+                .to_string(),
+        )
+    });
+
+    packages.insert(dir.to_path_buf(), name.clone());
+    name
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn assumed_names() {
+        assert_eq!(assumed_name("fmt"), "fmt");
+        assert_eq!(assumed_name("github.com/spf13/pflag"), "pflag");
+        assert_eq!(assumed_name("example.com/foo/v2"), "foo");
+        assert_eq!(assumed_name("gopkg.in/yaml.v3"), "yaml");
+        assert_eq!(assumed_name("github.com/mattn/go-isatty"), "isatty");
+        assert_eq!(assumed_name("github.com/foo/bar-baz"), "bar");
+    }
+
+    #[test]
+    fn resolved_names() {
+        let module = Module {
+            path: Some(String::from("github.com/brianstrauch/gold/tests")),
+            go_version: None,
+            dir: Some(PathBuf::from("tests")),
+        };
+
+        assert_eq!(
+            package_name("github.com/brianstrauch/gold/tests/F004/named", &module),
+            Some(String::from("renamed"))
+        );
+        assert_eq!(
+            package_name("github.com/brianstrauch/gold/tests/F00", &module),
+            None
+        );
+        assert_eq!(package_name("fmt", &module), Some(String::from("fmt")));
+        assert_eq!(
+            package_name("math/rand/v2", &module),
+            Some(String::from("rand"))
+        );
+        assert_eq!(package_name("k8s.io/api/core/v1", &module), None);
+    }
+}
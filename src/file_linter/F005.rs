@@ -0,0 +1,191 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use tree_sitter::{Node, Query, QueryCursor};
+use tree_sitter_edit::{NodeId, Replace};
+
+use crate::{error::Error, file_linter::tree_sitter_go};
+
+use super::FileLinter;
+
+lazy_static! {
+    static ref QUERY: Query = tree_sitter::Query::new(
+        unsafe { tree_sitter_go() },
+        "[(field_declaration_list) (var_declaration)] @list"
+    )
+    .unwrap();
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+#[schemars(rename = "F005Settings")]
+pub struct Settings {
+    /// Whether fields of the same type share it in structs with tags, default: grouped
+    #[serde(default)]
+    pub tagged: Style,
+}
+
+/// How fields of the same type are declared in structs with tags
+#[derive(Clone, Copy, Debug, Default, PartialEq, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+#[schemars(rename = "F005Style")]
+pub enum Style {
+    /// Consecutive fields of the same type share it, as in `a, b int`
+    #[default]
+    Grouped,
+    /// Every field has its own line, with the type and tag it would share
+    Explicit,
+}
+
+// A field or variable declaration with names and a type.
+struct Declaration<'a> {
+    node: Node<'a>,
+    names: Vec<&'a str>,
+    kind: Node<'a>,
+}
+
+// F005 - Redundant field and variable types
+pub fn run(linter: &mut FileLinter, settings: &Settings) -> (Vec<Error>, Vec<Replace>) {
+    let mut cursor = QueryCursor::new();
+
+    let mut errors = vec![];
+    let mut editors = vec![];
+
+    for m in cursor.matches(&QUERY, linter.tree.root_node(), linter.source.as_bytes()) {
+        let list = m.captures[0].node;
+        let noun = if list.kind() == "var_declaration" {
+            "variable"
+        } else {
+            "field"
+        };
+
+        let children: Vec<Node> = list.named_children(&mut list.walk()).collect();
+        let tagged = children
+            .iter()
+            .any(|child| child.child_by_field_name("tag").is_some());
+
+        // Fields of tagged structs are split, since a tag applies to every name of its field.
+        if tagged && settings.tagged == Style::Explicit {
+            for child in children {
+                let Some(declaration) = declaration(linter, child) else {
+                    continue;
+                };
+                if declaration.names.len() < 2 {
+                    continue;
+                }
+
+                let kind = linter.text(declaration.kind);
+                if linter.fix {
+                    editors.push(Replace {
+                        id: NodeId::new(&child),
+                        bytes: split(linter, &declaration).into_bytes(),
+                    });
+                } else {
+                    errors.push(Error {
+                        filename: linter.path.clone(),
+                        position: declaration.kind.start_position(),
+                        rule: String::from("F005"),
+                        message: format!(r#"grouped {noun} type "{kind}""#),
+                    });
+                }
+            }
+            continue;
+        }
+
+        // Declarations are merged when they follow each other on consecutive lines, with nothing
+        // but the type in common: no comments, tags or values.
+        let mut runs: Vec<Vec<Declaration>> = vec![];
+        let mut last: Option<Node> = None;
+        for (i, &child) in children.iter().enumerate() {
+            let commented = children.get(i + 1).is_some_and(|next| {
+                next.kind() == "comment" && next.start_position().row == child.end_position().row
+            });
+            let Some(declaration) = declaration(linter, child)
+                .filter(|d| d.node.child_by_field_name("tag").is_none())
+                .filter(|d| d.node.child_by_field_name("value").is_none())
+                .filter(|_| !commented)
+            else {
+                last = None;
+                continue;
+            };
+
+            let continues = last.is_some_and(|last| {
+                last.end_position().row + 1 == child.start_position().row
+                    && runs.last().is_some_and(|run| {
+                        linter.text(run[0].kind) == linter.text(declaration.kind)
+                    })
+            });
+            if continues {
+                runs.last_mut().unwrap().push(declaration);
+            } else {
+                runs.push(vec![declaration]);
+            }
+            last = Some(child);
+        }
+
+        for run in runs.iter().filter(|run| run.len() > 1) {
+            let kind = linter.text(run[0].kind);
+
+            if linter.fix {
+                let names: Vec<&str> = run.iter().flat_map(|d| d.names.clone()).collect();
+                for (i, declaration) in run.iter().enumerate() {
+                    editors.push(Replace {
+                        id: NodeId::new(&declaration.node),
+                        bytes: if i == 0 {
+                            format!("{} {kind}", names.join(", ")).into_bytes()
+                        } else {
+                            vec![]
+                        },
+                    });
+                }
+            } else {
+                for declaration in &run[..run.len() - 1] {
+                    errors.push(Error {
+                        filename: linter.path.clone(),
+                        position: declaration.kind.start_position(),
+                        rule: String::from("F005"),
+                        message: format!(r#"redundant {noun} type "{kind}""#),
+                    });
+                }
+            }
+        }
+    }
+
+    (errors, editors)
+}
+
+// Reads a field declaration or variable spec, unless it is an embedded field or has no type.
+fn declaration<'a>(linter: &'a FileLinter, node: Node<'a>) -> Option<Declaration<'a>> {
+    if node.kind() != "field_declaration" && node.kind() != "var_spec" {
+        return None;
+    }
+
+    let names: Vec<&str> = node
+        .children_by_field_name("name", &mut node.walk())
+        .map(|name| linter.text(name))
+        .collect();
+    let kind = node.child_by_field_name("type")?;
+
+    (!names.is_empty()).then_some(Declaration { node, names, kind })
+}
+
+// Gives every name of a field its own line, with the type and tag of the field.
+fn split(linter: &FileLinter, declaration: &Declaration) -> String {
+    let source = linter.source.as_str();
+    let start = declaration.node.start_byte();
+    let line = source[..start].rfind('\n').map_or(0, |i| i + 1);
+    let indentation = &source[line..start];
+    let newline = if source.contains("\r\n") {
+        "\r\n"
+    } else {
+        "\n"
+    };
+
+    let rest = &source[declaration.kind.start_byte()..declaration.node.end_byte()];
+    let fields: Vec<String> = declaration
+        .names
+        .iter()
+        .map(|name| format!("{name} {rest}"))
+        .collect();
+
+    fields.join(&format!("{newline}{indentation}"))
+}
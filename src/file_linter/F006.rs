@@ -0,0 +1,310 @@
+use std::{
+    collections::HashMap,
+    fmt::{self, Display},
+    mem,
+};
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use tree_sitter::Node;
+use tree_sitter_edit::{NodeId, Replace};
+
+use crate::error::Error;
+
+use super::{nolint, FileLinter};
+
+#[derive(Clone, Debug, Deserialize, Serialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+#[schemars(rename = "F006Settings")]
+pub struct Settings {
+    /// Order of top-level declarations, default: [const, var, type, constructor, method, func]
+    #[serde(default = "default_order")]
+    pub order: Vec<Kind>,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Settings {
+            order: default_order(),
+        }
+    }
+}
+
+fn default_order() -> Vec<Kind> {
+    vec![
+        Kind::Const,
+        Kind::Var,
+        Kind::Type,
+        Kind::Constructor,
+        Kind::Method,
+        Kind::Func,
+    ]
+}
+
+/// Kind of top-level declaration
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+#[schemars(rename = "F006Kind")]
+pub enum Kind {
+    /// Constant declarations
+    Const,
+    /// Variable declarations
+    Var,
+    /// Type declarations
+    Type,
+    /// Functions named New... or new... that return a type of the file
+    Constructor,
+    /// Methods
+    Method,
+    /// Other functions
+    Func,
+}
+
+impl Display for Kind {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Kind::Const => write!(formatter, "const"),
+            Kind::Var => write!(formatter, "var"),
+            Kind::Type => write!(formatter, "type"),
+            Kind::Constructor => write!(formatter, "constructor"),
+            Kind::Method => write!(formatter, "method"),
+            Kind::Func => write!(formatter, "func"),
+        }
+    }
+}
+
+// A top-level declaration with the comments on the lines right above it and at the end of its
+// line, which move with it.
+struct Declaration<'a> {
+    node: Node<'a>,
+    comments: Vec<Node<'a>>,
+    trailing: Option<Node<'a>>,
+    kind: Kind,
+    name: String,
+    // the type that a constructor or method belongs to
+    owner: Option<String>,
+}
+
+// F006 - Unordered declarations
+pub fn run(linter: &mut FileLinter, settings: &Settings) -> (Vec<Error>, Vec<Replace>) {
+    let mut errors = vec![];
+    let mut editors = vec![];
+
+    let directives = nolint::directives(linter);
+    let mut declarations = declarations(linter);
+
+    // Types are numbered in the order they are declared, so that their constructors and methods
+    // can be sorted right after them.
+    let mut types = HashMap::new();
+    for declaration in declarations.iter().filter(|d| d.kind == Kind::Type) {
+        for spec in declaration
+            .node
+            .named_children(&mut declaration.node.walk())
+        {
+            if let Some(name) = spec.child_by_field_name("name") {
+                let index = types.len();
+                types.entry(linter.text(name)).or_insert(index);
+            }
+        }
+    }
+
+    // A function is only a constructor if it returns a type of the file.
+    for declaration in &mut declarations {
+        if declaration.kind == Kind::Constructor
+            && !declaration
+                .owner
+                .as_ref()
+                .is_some_and(|owner| types.contains_key(owner.as_str()))
+        {
+            declaration.kind = Kind::Func;
+            declaration.owner = None;
+        }
+    }
+
+    let rank = |kind: Kind| settings.order.iter().position(|k| *k == kind);
+    let key = |declaration: &Declaration| {
+        let own_rank = rank(declaration.kind)?;
+        let owner = match declaration.kind {
+            Kind::Type => Some(declaration.name.as_str()),
+            _ => declaration.owner.as_deref(),
+        };
+
+        match owner.and_then(|owner| Some((rank(Kind::Type)?, types.get(owner)?))) {
+            Some((type_rank, index)) => Some((type_rank, *index, own_rank)),
+            None => Some((own_rank, 0, 0)),
+        }
+    };
+
+    // Declarations of kinds that are not ordered, or with suppressed issues, keep their place.
+    let checked: Vec<(&Declaration, (usize, usize, usize))> = declarations
+        .iter()
+        .filter(|d| {
+            !directives
+                .iter()
+                .any(|directive| directive.suppresses("F006", d.node.start_position().row))
+        })
+        .filter_map(|d| Some((d, key(d)?)))
+        .collect();
+
+    for (i, (declaration, key)) in checked.iter().enumerate() {
+        let Some((before, _)) = checked[..i].iter().find(|(_, k)| k > key) else {
+            continue;
+        };
+
+        errors.push(Error {
+            filename: linter.path.clone(),
+            position: declaration.node.start_position(),
+            rule: String::from("F006"),
+            message: format!(
+                r#"misplaced {} "{}", expected before {} "{}""#,
+                declaration.kind, declaration.name, before.kind, before.name
+            ),
+        });
+    }
+
+    if errors.is_empty() {
+        return (errors, editors);
+    }
+
+    // Each declaration is replaced by the one that belongs in its place, with its comments.
+    let mut sorted = checked.clone();
+    sorted.sort_by_key(|(_, key)| *key);
+
+    for ((declaration, _), (replacement, _)) in checked.iter().zip(&sorted) {
+        if declaration.node.id() == replacement.node.id() {
+            continue;
+        }
+
+        for comment in declaration.comments.iter().chain(&declaration.trailing) {
+            editors.push(Replace {
+                id: NodeId::new(comment),
+                bytes: vec![],
+            });
+        }
+
+        let start = replacement
+            .comments
+            .first()
+            .unwrap_or(&replacement.node)
+            .start_byte();
+        let end = replacement.trailing.unwrap_or(replacement.node).end_byte();
+        editors.push(Replace {
+            id: NodeId::new(&declaration.node),
+            bytes: linter.source.as_bytes()[start..end].to_vec(),
+        });
+    }
+
+    (errors, editors)
+}
+
+// Lists the top-level declarations of a file, other than imports.
+fn declarations<'a>(linter: &'a FileLinter) -> Vec<Declaration<'a>> {
+    let root = linter.tree.root_node();
+
+    let mut declarations = vec![];
+    let mut comments: Vec<Node> = vec![];
+    let mut end_row = None;
+
+    for node in root.named_children(&mut root.walk()) {
+        if node.kind() == "comment" {
+            let row = node.start_position().row;
+            if end_row == Some(row) {
+                if let Some(declaration) = declarations
+                    .last_mut()
+                    .filter(|d: &&mut Declaration| d.node.end_position().row == row)
+                {
+                    declaration.trailing = Some(node);
+                }
+                continue;
+            }
+
+            // Only comments on consecutive lines right above a declaration belong to it.
+            let adjacent = comments
+                .last()
+                .is_some_and(|last| last.end_position().row + 1 == node.start_position().row);
+            if !adjacent {
+                comments.clear();
+            }
+            comments.push(node);
+            continue;
+        }
+
+        end_row = Some(node.end_position().row);
+        let mut leading = mem::take(&mut comments);
+        if leading
+            .last()
+            .is_some_and(|last| last.end_position().row + 1 != node.start_position().row)
+        {
+            leading.clear();
+        }
+
+        // the first name declared by a const, var or type declaration
+        let name = |node: Node| {
+            node.named_children(&mut node.walk())
+                .find_map(|spec| spec.child_by_field_name("name"))
+                .map_or(String::new(), |name| linter.text(name).to_string())
+        };
+
+        let (kind, name, owner) = match node.kind() {
+            "const_declaration" => (Kind::Const, name(node), None),
+            "var_declaration" => (Kind::Var, name(node), None),
+            "type_declaration" => (Kind::Type, name(node), None),
+            "method_declaration" => {
+                let owner = node
+                    .child_by_field_name("receiver")
+                    .and_then(|receiver| receiver.named_child(0))
+                    .and_then(|receiver| receiver.child_by_field_name("type"))
+                    .map(|kind| base_type(linter.text(kind)));
+                let method = node
+                    .child_by_field_name("name")
+                    .map_or("", |name| linter.text(name));
+                let name = match &owner {
+                    Some(owner) => format!("{owner}.{method}"),
+                    None => method.to_string(),
+                };
+                (Kind::Method, name, owner)
+            }
+            "function_declaration" => {
+                let name = node
+                    .child_by_field_name("name")
+                    .map_or("", |name| linter.text(name));
+                let result = node
+                    .child_by_field_name("result")
+                    .map(|result| match result.kind() {
+                        "parameter_list" => result
+                            .named_child(0)
+                            .and_then(|parameter| parameter.child_by_field_name("type"))
+                            .map_or("", |kind| linter.text(kind)),
+                        _ => linter.text(result),
+                    });
+
+                match result.map(base_type) {
+                    Some(owner) if name.starts_with("New") || name.starts_with("new") => {
+                        (Kind::Constructor, name.to_string(), Some(owner))
+                    }
+                    _ => (Kind::Func, name.to_string(), None),
+                }
+            }
+            _ => continue,
+        };
+
+        declarations.push(Declaration {
+            node,
+            comments: leading,
+            trailing: None,
+            kind,
+            name,
+            owner,
+        });
+    }
+
+    declarations
+}
+
+// Strips pointers and type arguments from a type, as in `*T[K]`.
+fn base_type(kind: &str) -> String {
+    let kind = kind.trim_start_matches('*');
+    kind[..kind.find('[').unwrap_or(kind.len())]
+        .trim()
+        .to_string()
+}
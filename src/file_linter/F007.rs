@@ -0,0 +1,419 @@
+use std::{collections::HashSet, fs, path::Path};
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use tree_sitter::{Node, Query, QueryCursor, Tree};
+use tree_sitter_edit::{NodeId, Replace};
+
+use crate::{error::Error, file_linter::tree_sitter_go};
+
+use super::{parse, FileLinter};
+
+lazy_static! {
+    static ref QUERY: Query = tree_sitter::Query::new(
+        unsafe { tree_sitter_go() },
+        r#"
+        (function_declaration name: (identifier) @func)
+        (method_declaration name: (field_identifier) @method)
+        (type_spec name: (type_identifier) @type)
+        (type_alias name: (type_identifier) @type)
+        (const_spec name: (identifier) @const)
+        (var_spec name: (identifier) @var)
+        (short_var_declaration left: (expression_list (identifier) @var))
+        (parameter_declaration name: (identifier) @parameter)
+        (variadic_parameter_declaration name: (identifier) @parameter)
+        (field_declaration name: (field_identifier) @field)
+        "#
+    )
+    .unwrap();
+    static ref IDENTIFIERS: Query = tree_sitter::Query::new(
+        unsafe { tree_sitter_go() },
+        "[(identifier) (field_identifier) (type_identifier)] @identifier"
+    )
+    .unwrap();
+}
+
+// The initialisms that golint knows.
+const INITIALISMS: [&str; 38] = [
+    "ACL", "API", "ASCII", "CPU", "CSS", "DNS", "EOF", "GUID", "HTML", "HTTP", "HTTPS", "ID", "IP",
+    "JSON", "LHS", "QPS", "RAM", "RHS", "RPC", "SLA", "SMTP", "SQL", "SSH", "TCP", "TLS", "TTL",
+    "UDP", "UI", "UID", "UUID", "URI", "URL", "UTF8", "VM", "XML", "XMPP", "XSRF", "XSS",
+];
+
+#[derive(Clone, Debug, Deserialize, Serialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+#[schemars(rename = "F007Settings")]
+pub struct Settings {
+    /// Initialisms to write in a consistent case, like `URL` or `url`, default: those of golint
+    #[serde(default = "default_initialisms")]
+    pub initialisms: Vec<String>,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Settings {
+            initialisms: default_initialisms(),
+        }
+    }
+}
+
+fn default_initialisms() -> Vec<String> {
+    INITIALISMS.iter().map(|i| i.to_string()).collect()
+}
+
+// F007 - Non-idiomatic names
+pub fn run(linter: &mut FileLinter, settings: &Settings) -> (Vec<Error>, Vec<Replace>) {
+    let mut errors = vec![];
+    let mut editors = vec![];
+
+    let initialisms: HashSet<String> = settings
+        .initialisms
+        .iter()
+        .map(|i| i.to_uppercase())
+        .collect();
+    let is_test = linter.path.ends_with("_test.go");
+
+    let mut cursor = QueryCursor::new();
+    let identifiers: Vec<_> = cursor
+        .matches(
+            &IDENTIFIERS,
+            linter.tree.root_node(),
+            linter.source.as_bytes(),
+        )
+        .map(|m| m.captures[0].node)
+        .collect();
+
+    let mut renamed = HashSet::new();
+    let mut edited = HashSet::new();
+    // the identifiers of the other files of the package, read when they are first needed
+    let mut package_identifiers: Option<HashSet<String>> = None;
+
+    let mut cursor = QueryCursor::new();
+    for m in cursor.matches(&QUERY, linter.tree.root_node(), linter.source.as_bytes()) {
+        let capture = m.captures[0];
+        let what = QUERY.capture_names()[capture.index as usize].as_str();
+        let name = linter.text(capture.node);
+
+        if name.starts_with('_') {
+            continue;
+        }
+
+        // Tests, benchmarks, examples and fuzz tests can use underscores, as in `TestF_g`.
+        if is_test
+            && what == "func"
+            && ["Test", "Benchmark", "Example", "Fuzz"]
+                .iter()
+                .any(|prefix| name.starts_with(prefix))
+        {
+            continue;
+        }
+
+        if name.len() >= 5
+            && name.contains('_')
+            && name
+                .chars()
+                .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_')
+        {
+            errors.push(Error {
+                filename: linter.path.clone(),
+                position: capture.node.start_position(),
+                rule: String::from("F007"),
+                message: format!(r#"{what} "{name}" should use MixedCaps instead of ALL_CAPS"#),
+            });
+            continue;
+        }
+
+        let should = lint_name(name, &initialisms);
+        if should == name {
+            continue;
+        }
+
+        errors.push(Error {
+            filename: linter.path.clone(),
+            position: capture.node.start_position(),
+            rule: String::from("F007"),
+            message: format!(r#"{what} "{name}" should be "{should}""#),
+        });
+
+        // Parameters, results and variables of a function are renamed throughout the function,
+        // and the parameters of a function type or interface method throughout their list.
+        // Exported names can be used by other packages, so they are only reported, and any other
+        // name is renamed throughout the file, unless another file of the package refers to it.
+        let local = match what {
+            "parameter" => Some(function(capture.node).unwrap_or(capture.node.parent().unwrap())),
+            "var" => function(capture.node),
+            _ => None,
+        };
+        let scope = match local {
+            Some(scope) => scope,
+            None if name.starts_with(|c: char| c.is_uppercase()) => continue,
+            None => {
+                let package = package_identifiers.get_or_insert_with(|| package(linter));
+                if package.contains(name) || package.contains(&should) {
+                    continue;
+                }
+                linter.tree.root_node()
+            }
+        };
+
+        let in_scope: Vec<Node> = identifiers
+            .iter()
+            .filter(|node| scope.byte_range().contains(&node.start_byte()))
+            .copied()
+            .collect();
+        if !renamed.insert((scope.id(), name))
+            || in_scope.iter().any(|node| linter.text(*node) == should)
+        {
+            continue;
+        }
+
+        // Within a function, only variables are renamed, and not the field names of struct
+        // literals. A key of a literal whose type is not known could be either, so the name is
+        // left as it is.
+        let mut nodes = vec![];
+        let mut ambiguous = false;
+        for node in in_scope
+            .into_iter()
+            .filter(|node| linter.text(*node) == name)
+        {
+            if local.is_none() {
+                nodes.push(node);
+                continue;
+            }
+            if node.kind() != "identifier" {
+                continue;
+            }
+            match key(linter, node) {
+                Some(Key::Field) => {}
+                Some(Key::Unknown) => ambiguous = true,
+                Some(Key::Expression) | None => nodes.push(node),
+            }
+        }
+        if ambiguous {
+            continue;
+        }
+
+        for node in nodes {
+            if edited.insert(node.id()) {
+                editors.push(Replace {
+                    id: NodeId::new(&node),
+                    bytes: should.as_bytes().to_vec(),
+                });
+            }
+        }
+    }
+
+    (errors, editors)
+}
+
+// Finds the function, method or function literal that a name is declared in.
+fn function(node: Node) -> Option<Node> {
+    let mut node = node.parent();
+    while let Some(parent) = node {
+        if matches!(
+            parent.kind(),
+            "function_declaration" | "method_declaration" | "func_literal"
+        ) {
+            return Some(parent);
+        }
+        node = parent.parent();
+    }
+    None
+}
+
+// What the key of a composite literal element is: the name of a struct field, as `a` in `T{a: 1}`,
+// an expression, as in a map literal, or either when the type of the literal is not known.
+enum Key {
+    Field,
+    Expression,
+    Unknown,
+}
+
+// Finds what an identifier is if it is the key of a composite literal element.
+fn key(linter: &FileLinter, node: Node) -> Option<Key> {
+    let element = node.parent().filter(|p| p.kind() == "literal_element")?;
+    let keyed = element.parent().filter(|p| p.kind() == "keyed_element")?;
+    if keyed.named_child(0)?.id() != element.id() {
+        return None;
+    }
+
+    // The type of a literal inside another one can be elided, as in `[]T{{a: 1}}`.
+    let kind = keyed
+        .parent()
+        .and_then(|literal| literal.parent())
+        .filter(|parent| parent.kind() == "composite_literal")
+        .and_then(|composite| composite.child_by_field_name("type"));
+
+    Some(kind.map_or(Key::Unknown, |kind| key_of(linter, kind, 0)))
+}
+
+// Finds what the keys of a literal of a type are, following the types declared in the file.
+fn key_of(linter: &FileLinter, kind: Node, depth: usize) -> Key {
+    match kind.kind() {
+        "struct_type" => Key::Field,
+        "map_type" | "slice_type" | "array_type" | "implicit_length_array_type" => Key::Expression,
+        "type_identifier" | "generic_type" if depth < 10 => {
+            let name = match kind.kind() {
+                "generic_type" => kind.child_by_field_name("type").map(|t| linter.text(t)),
+                _ => Some(linter.text(kind)),
+            };
+
+            let root = linter.tree.root_node();
+            root.named_children(&mut root.walk())
+                .filter(|node| node.kind() == "type_declaration")
+                .flat_map(|node| node.named_children(&mut node.walk()).collect::<Vec<_>>())
+                .find(|spec| spec.child_by_field_name("name").map(|n| linter.text(n)) == name)
+                .and_then(|spec| spec.child_by_field_name("type"))
+                .map_or(Key::Unknown, |kind| key_of(linter, kind, depth + 1))
+        }
+        _ => Key::Unknown,
+    }
+}
+
+// Lists the identifiers of the other files of the package of a file, in the same directory.
+fn package(linter: &FileLinter) -> HashSet<String> {
+    let path = Path::new(&linter.path);
+    let dir = path
+        .parent()
+        .filter(|dir| !dir.as_os_str().is_empty())
+        .unwrap_or(Path::new("."));
+    let package = package_name(&linter.tree, &linter.source);
+
+    let mut identifiers = HashSet::new();
+    let Ok(entries) = fs::read_dir(dir) else {
+        return identifiers;
+    };
+    for entry in entries.filter_map(|e| e.ok()) {
+        let file = entry.path();
+        if file.extension().is_none_or(|extension| extension != "go")
+            || file.file_name() == path.file_name()
+        {
+            continue;
+        }
+        let Ok(source) = fs::read_to_string(&file) else {
+            continue;
+        };
+
+        let tree = parse(&source);
+        if package_name(&tree, &source) != package {
+            continue;
+        }
+        let mut cursor = QueryCursor::new();
+        for m in cursor.matches(&IDENTIFIERS, tree.root_node(), source.as_bytes()) {
+            if let Ok(text) = m.captures[0].node.utf8_text(source.as_bytes()) {
+                identifiers.insert(text.to_string());
+            }
+        }
+    }
+
+    identifiers
+}
+
+// Reads the package clause of a file.
+fn package_name(tree: &Tree, source: &str) -> Option<String> {
+    let root = tree.root_node();
+    root.named_children(&mut root.walk())
+        .find(|node| node.kind() == "package_clause")
+        .and_then(|clause| clause.named_child(0))
+        .and_then(|name| name.utf8_text(source.as_bytes()).ok())
+        .map(String::from)
+}
+
+// Suggests the MixedCaps form of a name, with initialisms in a consistent case, like golint does:
+// words are split at underscores and where a lowercase letter is followed by another character.
+fn lint_name(name: &str, initialisms: &HashSet<String>) -> String {
+    if name == "_" || name.chars().all(char::is_lowercase) {
+        return name.to_string();
+    }
+
+    let mut runes: Vec<char> = name.chars().collect();
+    // the start of the current word
+    let mut w = 0;
+    let mut i = 0;
+
+    while i < runes.len() {
+        let mut eow = false;
+        if i + 1 == runes.len() {
+            eow = true;
+        } else if runes[i + 1] == '_' {
+            // Underscores are removed, except for one between two digits.
+            eow = true;
+            let mut n = 1;
+            while i + n + 1 < runes.len() && runes[i + n + 1] == '_' {
+                n += 1;
+            }
+            if i + n + 1 < runes.len()
+                && runes[i].is_ascii_digit()
+                && runes[i + n + 1].is_ascii_digit()
+            {
+                n -= 1;
+            }
+            runes.drain(i + 1..i + 1 + n);
+        } else if runes[i].is_lowercase() && !runes[i + 1].is_lowercase() {
+            eow = true;
+        }
+        i += 1;
+
+        if !eow {
+            continue;
+        }
+
+        let word: String = runes[w..i].iter().collect();
+        let upper = word.to_uppercase();
+        if initialisms.contains(&upper) {
+            // Initialisms are lowercase only at the start of an unexported name.
+            let replacement = if w == 0 && runes[w].is_lowercase() {
+                upper.to_lowercase()
+            } else {
+                upper
+            };
+            runes.splice(w..i, replacement.chars());
+        } else if w > 0 && word.to_lowercase() == word {
+            runes[w] = runes[w].to_uppercase().next().unwrap();
+        }
+        w = i;
+    }
+
+    runes.into_iter().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lint_names() {
+        let initialisms: HashSet<String> = default_initialisms().into_iter().collect();
+        let lint = |name| lint_name(name, &initialisms);
+
+        assert_eq!(lint("foo_bar"), "fooBar");
+        assert_eq!(lint("foo_bar_baz"), "fooBarBaz");
+        assert_eq!(lint("Foo_bar"), "FooBar");
+        assert_eq!(lint("foo_WiFi"), "fooWiFi");
+        assert_eq!(lint("id"), "id");
+        assert_eq!(lint("Id"), "ID");
+        assert_eq!(lint("foo_id"), "fooID");
+        assert_eq!(lint("fooId"), "fooID");
+        assert_eq!(lint("fooUid"), "fooUID");
+        assert_eq!(lint("idFoo"), "idFoo");
+        assert_eq!(lint("uidFoo"), "uidFoo");
+        assert_eq!(lint("midIdDle"), "midIDDle");
+        assert_eq!(lint("APIProxy"), "APIProxy");
+        assert_eq!(lint("ApiProxy"), "APIProxy");
+        assert_eq!(lint("apiProxy"), "apiProxy");
+        assert_eq!(lint("_Leading"), "_Leading");
+        assert_eq!(lint("___Leading"), "_Leading");
+        assert_eq!(lint("trailing_"), "trailing");
+        assert_eq!(lint("trailing___"), "trailing");
+        assert_eq!(lint("a_b"), "aB");
+        assert_eq!(lint("a__b"), "aB");
+        assert_eq!(lint("a___b"), "aB");
+        assert_eq!(lint("Rpc1150"), "RPC1150");
+        assert_eq!(lint("case3_1"), "case3_1");
+        assert_eq!(lint("case3__1"), "case3_1");
+        assert_eq!(lint("IEEE802_16bit"), "IEEE802_16bit");
+        assert_eq!(lint("IEEE802_16Bit"), "IEEE802_16Bit");
+        assert_eq!(lint("HttpServer"), "HTTPServer");
+        assert_eq!(lint("Url"), "URL");
+    }
+}
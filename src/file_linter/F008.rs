@@ -0,0 +1,154 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use tree_sitter::{Node, Query, QueryCursor};
+use tree_sitter_edit::Replace;
+
+use crate::{error::Error, file_linter::tree_sitter_go};
+
+use super::FileLinter;
+
+lazy_static! {
+    static ref QUERY: Query = tree_sitter::Query::new(
+        unsafe { tree_sitter_go() },
+        r#"
+        (source_file (function_declaration name: (identifier) @func))
+        (source_file (method_declaration name: (field_identifier) @method))
+        (source_file (type_declaration (type_spec name: (type_identifier) @type)))
+        (source_file (type_declaration (type_alias name: (type_identifier) @type)))
+        (source_file (const_declaration (const_spec name: (identifier) @const)))
+        (source_file (var_declaration (var_spec name: (identifier) @var)))
+        "#
+    )
+    .unwrap();
+    static ref PACKAGE_QUERY: Query = tree_sitter::Query::new(
+        unsafe { tree_sitter_go() },
+        "(package_clause (package_identifier) @name)"
+    )
+    .unwrap();
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+#[schemars(rename = "F008Settings")]
+pub struct Settings {
+    /// Names allowed to stutter or to be getters, like `UserService` or `Client.GetName`, default: []
+    #[serde(default)]
+    pub exceptions: Vec<String>,
+}
+
+// F008 - Stuttering names and getters
+pub fn run(linter: &mut FileLinter, settings: &Settings) -> (Vec<Error>, Vec<Replace>) {
+    let mut errors = vec![];
+
+    let mut cursor = QueryCursor::new();
+    let package = cursor
+        .matches(
+            &PACKAGE_QUERY,
+            linter.tree.root_node(),
+            linter.source.as_bytes(),
+        )
+        .next()
+        .map_or("", |m| linter.text(m.captures[0].node));
+
+    // Commands are not imported, and neither are the external test packages of other packages.
+    let is_imported = package != "main" && !package.ends_with("_test");
+
+    let mut cursor = QueryCursor::new();
+    for m in cursor.matches(&QUERY, linter.tree.root_node(), linter.source.as_bytes()) {
+        let capture = m.captures[0];
+        let what = QUERY.capture_names()[capture.index as usize].as_str();
+        let name = linter.text(capture.node);
+
+        if !name.starts_with(|c: char| c.is_uppercase()) {
+            continue;
+        }
+
+        let declaration = capture.node.parent().unwrap();
+        let qualified = match receiver(linter, declaration) {
+            Some(receiver) => format!("{receiver}.{name}"),
+            None => name.to_string(),
+        };
+        if settings
+            .exceptions
+            .iter()
+            .any(|exception| *exception == name || *exception == qualified)
+        {
+            continue;
+        }
+
+        if what != "method" && is_imported {
+            if let Some(should) = unstuttered(name, package) {
+                errors.push(Error {
+                    filename: linter.path.clone(),
+                    position: capture.node.start_position(),
+                    rule: String::from("F008"),
+                    message: format!(
+                        r#"{what} "{name}" stutters as "{package}.{name}", should be "{should}""#
+                    ),
+                });
+            }
+        }
+
+        if (what == "func" || what == "method") && is_getter(declaration) {
+            if let Some(should) = name.strip_prefix("Get").filter(|rest| is_word(rest)) {
+                errors.push(Error {
+                    filename: linter.path.clone(),
+                    position: capture.node.start_position(),
+                    rule: String::from("F008"),
+                    message: format!(r#"getter "{name}" should be "{should}""#),
+                });
+            }
+        }
+    }
+
+    (errors, vec![])
+}
+
+// Finds the name that a declaration would have without the package name it starts with, as in
+// `user.UserService`. Like golint, the package name has to be followed by a new word.
+fn unstuttered<'a>(name: &'a str, package: &str) -> Option<&'a str> {
+    let prefix = name.get(..package.len())?;
+    let rest = &name[package.len()..];
+    (!package.is_empty() && prefix.eq_ignore_ascii_case(package) && is_word(rest)).then_some(rest)
+}
+
+// Checks whether the rest of a name starts a new word, as `Name` does in `GetName`.
+fn is_word(rest: &str) -> bool {
+    rest.starts_with(|c: char| c.is_uppercase())
+}
+
+// Checks whether a function or method takes no parameters and returns something.
+fn is_getter(declaration: Node) -> bool {
+    let has_parameters = declaration
+        .child_by_field_name("parameters")
+        .is_some_and(|parameters| parameters.named_child_count() > 0);
+    let has_result = declaration.child_by_field_name("result").is_some();
+
+    !has_parameters && has_result
+}
+
+// Reads the base type of the receiver of a method, as in `func (c *Client[T]) Name()`.
+fn receiver<'a>(linter: &'a FileLinter, declaration: Node) -> Option<&'a str> {
+    let kind = declaration
+        .child_by_field_name("receiver")?
+        .named_child(0)?
+        .child_by_field_name("type")?;
+    let kind = linter.text(kind).trim_start_matches('*');
+
+    Some(kind[..kind.find('[').unwrap_or(kind.len())].trim())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stutter() {
+        assert_eq!(unstuttered("UserService", "user"), Some("Service"));
+        assert_eq!(unstuttered("HTTPClient", "http"), Some("Client"));
+        assert_eq!(unstuttered("User", "user"), None);
+        assert_eq!(unstuttered("Userspace", "user"), None);
+        assert_eq!(unstuttered("Service", "user"), None);
+        assert_eq!(unstuttered("Us", "user"), None);
+    }
+}
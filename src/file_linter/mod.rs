@@ -2,16 +2,216 @@
 
 pub mod F001;
 pub mod F002;
+pub mod F003;
+pub mod F004;
+pub mod F005;
+pub mod F006;
+pub mod F007;
+pub mod F008;
+pub mod nolint;
+pub mod stdlib;
 
-use std::fs::{self, File};
+use std::fs;
 
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
 use tree_sitter::{Node, Parser, Tree};
+use tree_sitter_edit::{Edit, Editor, NodeId, Replace};
 
-use crate::configuration::Configuration;
+use crate::{configuration::Configuration, error::Error, module_linter::Module};
+
+type Rule = fn(&mut FileLinter) -> (Vec<Error>, Vec<Replace>);
+
+// Registers rules by module name. Each rule module declares a `Settings` type, which is
+// deserialized under its code in the settings of a configuration file, and a `run` function,
+// which receives the settings that apply to the file being linted.
+macro_rules! rules {
+    ($($rule:ident),*) => {
+        pub const RULES: [&str; [$(stringify!($rule)),*].len()] = [$(stringify!($rule)),*];
+
+        const RUN: [Rule; RULES.len()] = [$(|linter| {
+            let settings = linter
+                .configuration
+                .settings
+                .as_ref()
+                .and_then(|settings| settings.$rule.clone())
+                .unwrap_or_default();
+            $rule::run(linter, &settings)
+        }),*];
+
+        /// Rule-specific settings
+        #[derive(Clone, Debug, Default, Deserialize, Serialize, JsonSchema)]
+        #[serde(deny_unknown_fields)]
+        pub struct Settings {
+            $(
+                #[doc = concat!("Settings of ", stringify!($rule))]
+                #[serde(skip_serializing_if = "Option::is_none")]
+                pub $rule: Option<$rule::Settings>,
+            )*
+        }
+
+        impl Settings {
+            // The default settings of every rule.
+            pub fn defaults() -> Self {
+                Settings {
+                    $($rule: Some($rule::Settings::default()),)*
+                }
+            }
+
+            // Refines these settings rule by rule.
+            pub fn merge(self, child: Settings) -> Self {
+                Settings {
+                    $($rule: child.$rule.or(self.$rule),)*
+                }
+            }
+        }
+    };
+}
+
+rules!(F001, F002, F003, F004, F005, F006, F007, F008);
+
+// Applies the edits of every rule in a single pass over the file.
+struct Editors<'a>(&'a [Replace]);
+
+impl Editor for Editors<'_> {
+    fn has_edit(&self, tree: &Tree, node: &Node<'_>) -> bool {
+        self.0.iter().any(|editor| editor.has_edit(tree, node))
+    }
+
+    fn edit(&self, source: &[u8], tree: &Tree, node: &Node<'_>) -> Vec<u8> {
+        let editor = self.0.iter().find(|editor| editor.has_edit(tree, node));
+        editor.unwrap().edit(source, tree, node)
+    }
+
+    // Removing a node that is alone on its line removes the line, and the blank line that would
+    // otherwise be left next to another one.
+    fn in_order_edits<'a>(
+        &'a self,
+        source: &'a [u8],
+        tree: &'a Tree,
+    ) -> Box<dyn Iterator<Item = Edit> + 'a> {
+        let mut edits: Vec<Edit> = vec![];
+
+        let mut cursor = tree.walk();
+        'traversal: loop {
+            let node = cursor.node();
+            if self.has_edit(tree, &node) {
+                let edit = Edit {
+                    position: node.start_byte(),
+                    delete: node.end_byte() - node.start_byte(),
+                    insert: self.edit(source, tree, &node),
+                };
+
+                // Removals on consecutive lines, or on the same line, are one removal, so that the
+                // lines between them go too. A blank line between removals stays, since it can
+                // separate what is around them.
+                match edits.last_mut() {
+                    Some(last)
+                        if last.insert.is_empty()
+                            && edit.insert.is_empty()
+                            && is_line_break(
+                                &source[last.position + last.delete..edit.position],
+                            ) =>
+                    {
+                        last.delete = edit.position + edit.delete - last.position;
+                    }
+                    _ => edits.push(edit),
+                }
+            } else if cursor.goto_first_child() {
+                continue;
+            }
+
+            while !cursor.goto_next_sibling() {
+                if !cursor.goto_parent() {
+                    break 'traversal;
+                }
+            }
+        }
+
+        // Edits must not overlap once they are extended to whole lines.
+        let mut end = 0;
+        Box::new(edits.into_iter().map(move |edit| {
+            let mut edit = delete_line(source, edit);
+            if edit.position < end {
+                edit.delete -= (end - edit.position).min(edit.delete);
+                edit.position = end;
+            }
+            end = edit.position + edit.delete;
+            edit
+        }))
+    }
+}
+
+// Checks whether text is whitespace with at most one line break.
+fn is_line_break(text: &[u8]) -> bool {
+    text.iter().all(u8::is_ascii_whitespace) && text.iter().filter(|b| **b == b'\n').count() <= 1
+}
+
+// Extends the removal of a node to its whole line when nothing else is on the line, and to the
+// whitespace before it when it ends a line.
+fn delete_line(source: &[u8], edit: Edit) -> Edit {
+    if !edit.insert.is_empty() {
+        return edit;
+    }
+
+    let is_blank = |bytes: &[u8]| bytes.iter().all(|b| b" \t\r".contains(b));
+
+    let mut start = source[..edit.position]
+        .iter()
+        .rposition(|b| *b == b'\n')
+        .map_or(0, |i| i + 1);
+    let end = edit.position + edit.delete;
+    let Some(mut end) = source[end..]
+        .iter()
+        .position(|b| *b == b'\n')
+        .map(|i| end + i + 1)
+    else {
+        return edit;
+    };
+
+    if !is_blank(&source[edit.position + edit.delete..end - 1]) {
+        return edit;
+    }
+
+    if !is_blank(&source[start..edit.position]) {
+        let position = source[..edit.position]
+            .iter()
+            .rposition(|b| !b" \t".contains(b))
+            .map_or(0, |i| i + 1);
+        return Edit {
+            position,
+            delete: edit.position + edit.delete - position,
+            insert: vec![],
+        };
+    }
+
+    let previous = source[..start.saturating_sub(1)]
+        .iter()
+        .rposition(|b| *b == b'\n')
+        .map_or(0, |i| i + 1);
+    let next = source[end..].iter().position(|b| *b == b'\n');
+    let next_blank = next.map_or(end == source.len(), |next| {
+        is_blank(&source[end..end + next])
+    });
+    if next_blank {
+        if start > 0 && is_blank(&source[previous..start - 1]) {
+            start = previous;
+        } else if let (0, Some(next)) = (start, next) {
+            end += next + 1;
+        }
+    }
+
+    Edit {
+        position: start,
+        delete: end - start,
+        insert: vec![],
+    }
+}
 
 pub struct FileLinter<'a> {
     pub path: String,
     pub fix: bool,
+    pub module: &'a Module,
     pub configuration: &'a Configuration,
     pub source: String,
     pub tree: Tree,
@@ -21,17 +221,29 @@ extern "C" {
     fn tree_sitter_go() -> tree_sitter::Language;
 }
 
-impl<'a> FileLinter<'a> {
-    pub fn new(path: String, fix: bool, configuration: &'a Configuration) -> Self {
-        let mut parser = Parser::new();
-        parser.set_language(unsafe { tree_sitter_go() }).unwrap();
+// The number of times the rules run on a file to fix it.
+const MAX_FIX_PASSES: usize = 10;
+
+fn parse(source: &str) -> Tree {
+    let mut parser = Parser::new();
+    parser.set_language(unsafe { tree_sitter_go() }).unwrap();
+    parser.parse(source, None).unwrap()
+}
 
+impl<'a> FileLinter<'a> {
+    pub fn new(
+        path: String,
+        fix: bool,
+        module: &'a Module,
+        configuration: &'a Configuration,
+    ) -> Self {
         let source = fs::read_to_string(&path).unwrap();
-        let tree = parser.parse(&source, None).unwrap();
+        let tree = parse(&source);
 
         FileLinter {
             fix,
             path,
+            module,
             configuration,
             source,
             tree,
@@ -39,32 +251,103 @@ impl<'a> FileLinter<'a> {
     }
 
     pub fn run(&mut self) -> bool {
-        let mut all_errors = vec![];
-        let mut all_editors = vec![];
+        let (errors, mut editors) = self.lint();
 
-        let rules = vec![F001::run, F002::run];
-        for rule in rules {
-            let (errors, editors) = &mut rule(self);
-            all_errors.append(errors);
-            all_editors.append(editors);
+        if !self.fix {
+            for error in errors.iter() {
+                println!("{error}");
+            }
+            return errors.is_empty();
         }
 
-        if self.fix {
-            for editor in all_editors.iter().rev() {
-                let source = fs::read_to_string(&self.path).unwrap();
-                let mut w = File::create(&self.path).unwrap();
-                tree_sitter_edit::render(&mut w, &self.tree, source.as_bytes(), editor).unwrap();
+        // The fix of one rule can hide that of another in the same node, so the rules run again on
+        // the fixed file until it no longer changes.
+        let mut changed = false;
+        for _ in 0..MAX_FIX_PASSES {
+            if editors.is_empty() {
+                break;
             }
-        } else {
-            for error in all_errors.iter() {
-                println!("{error}");
+
+            let mut fixed = vec![];
+            tree_sitter_edit::render(
+                &mut fixed,
+                &self.tree,
+                self.source.as_bytes(),
+                &Editors(&editors),
+            )
+            .unwrap();
+            if fixed == self.source.as_bytes() {
+                break;
+            }
+
+            self.source = String::from_utf8(fixed).unwrap();
+            self.tree = parse(&self.source);
+            changed = true;
+
+            editors = self.lint().1;
+        }
+
+        if changed {
+            fs::write(&self.path, &self.source).unwrap();
+        }
+
+        errors.is_empty()
+    }
+
+    // Runs the enabled rules, returning the errors and fixes that are not suppressed.
+    fn lint(&mut self) -> (Vec<Error>, Vec<Replace>) {
+        let mut all_errors = vec![];
+        let mut all_editors = vec![];
+
+        let directives = nolint::directives(self);
+        let is_suppressed =
+            |rule: &str, row: usize| directives.iter().any(|d| d.suppresses(rule, row));
+
+        for (code, rule) in RULES.iter().zip(RUN) {
+            if !self.configuration.is_enabled(code)
+                || self.configuration.is_excluded(&self.path, code)
+            {
+                continue;
             }
+
+            let (mut errors, mut editors) = rule(self);
+            errors.retain(|error| !is_suppressed(code, error.position.row));
+            editors.retain(|editor| {
+                self.find(editor.id)
+                    .is_none_or(|node| !is_suppressed(code, node.start_position().row))
+            });
+
+            all_errors.append(&mut errors);
+            all_editors.append(&mut editors);
         }
 
-        all_errors.is_empty()
+        all_errors.retain(|error| {
+            let source = self.source.lines().nth(error.position.row).unwrap_or("");
+            !self.configuration.is_excluded_error(error, source)
+        });
+
+        (all_errors, all_editors)
     }
 
     pub fn text(&self, node: Node) -> &str {
         node.utf8_text(self.source.as_bytes()).unwrap()
     }
+
+    pub fn find(&self, id: NodeId) -> Option<Node<'_>> {
+        let mut cursor = self.tree.walk();
+
+        loop {
+            if id.is(&cursor.node()) {
+                return Some(cursor.node());
+            }
+
+            if !cursor.goto_first_child() {
+                while !cursor.goto_next_sibling() {
+                    if !cursor.goto_parent() {
+                        return None;
+                    }
+                }
+            }
+        }
+    }
 }
@@ -0,0 +1,101 @@
+use regex::Regex;
+use tree_sitter::{Node, Query, QueryCursor};
+
+use crate::{configuration::golangci, file_linter::tree_sitter_go};
+
+use super::{FileLinter, RULES};
+
+lazy_static! {
+    static ref QUERY: Query =
+        tree_sitter::Query::new(unsafe { tree_sitter_go() }, "(comment) @comment").unwrap();
+    static ref NOLINT_PATTERN: Regex =
+        Regex::new(r"^//[/\s]*nolint(?::([\w-]+(?:\s*,\s*[\w-]+)*))?(?:\s|$)").unwrap();
+}
+
+// A //nolint directive, suppressing the issues of some rules on a range of lines.
+pub struct Directive {
+    rules: Vec<String>,
+    start: usize,
+    end: usize,
+}
+
+impl Directive {
+    pub fn suppresses(&self, rule: &str, row: usize) -> bool {
+        self.start <= row && row <= self.end && self.rules.iter().any(|r| r == rule)
+    }
+}
+
+// Parses the //nolint directives in a file, scoped like golangci-lint does: a directive at the
+// end of a line applies to that line, and a directive on its own line applies to the
+// declaration or statement that follows it, or to the whole file if that is the package clause.
+pub fn directives(linter: &FileLinter) -> Vec<Directive> {
+    let mut directives = vec![];
+
+    let mut cursor = QueryCursor::new();
+    for m in cursor.matches(&QUERY, linter.tree.root_node(), linter.source.as_bytes()) {
+        let comment = m.captures[0].node;
+
+        let Some(captures) = NOLINT_PATTERN.captures(linter.text(comment)) else {
+            continue;
+        };
+
+        let rules = match captures.get(1) {
+            None => all(),
+            Some(linters) => {
+                let linters: Vec<&str> = linters.as_str().split(',').map(str::trim).collect();
+                if linters.contains(&"all") {
+                    all()
+                } else {
+                    let mut rules = golangci::rules(&linters);
+                    rules.extend(
+                        RULES
+                            .iter()
+                            .filter(|rule| linters.contains(rule))
+                            .map(|rule| rule.to_string()),
+                    );
+                    rules
+                }
+            }
+        };
+
+        let (start, end) = scope(linter, comment);
+        directives.push(Directive { rules, start, end });
+    }
+
+    directives
+}
+
+fn all() -> Vec<String> {
+    RULES.iter().map(|rule| rule.to_string()).collect()
+}
+
+fn scope(linter: &FileLinter, comment: Node) -> (usize, usize) {
+    let start = comment.start_position().row;
+    let end = comment.end_position().row;
+
+    let line_start = linter.source[..comment.start_byte()]
+        .rfind('\n')
+        .map_or(0, |i| i + 1);
+    if !linter.source[line_start..comment.start_byte()]
+        .trim()
+        .is_empty()
+    {
+        return (start, end);
+    }
+
+    let mut next = comment.next_named_sibling();
+    while let Some(node) = next.filter(|node| node.kind() == "comment") {
+        next = node.next_named_sibling();
+    }
+
+    match next {
+        Some(node) if node.start_position().row == end + 1 => {
+            if node.kind() == "package_clause" {
+                (0, usize::MAX)
+            } else {
+                (start, node.end_position().row)
+            }
+        }
+        _ => (start, end),
+    }
+}
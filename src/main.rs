@@ -2,32 +2,53 @@
 extern crate lazy_static;
 extern crate simple_error;
 
+mod commands;
 mod configuration;
 mod error;
 mod file_linter;
 mod module_linter;
 
-use module_linter::ModuleLinter;
+use configuration::normalize;
+use module_linter::{find_module, lint_loose, workspace::Workspace, ModuleLinter};
 use simple_error::{bail, SimpleError};
-use std::{env, process::ExitCode};
+use std::{
+    env,
+    path::{Path, PathBuf},
+    process::ExitCode,
+    slice,
+};
 use walkdir::WalkDir;
 
 fn main() -> ExitCode {
     let args: Vec<String> = env::args().collect();
 
     let result;
-    if args.len() == 1 {
-        result = lint(".", false);
-    } else if args.len() == 2 {
-        if args[1] == "--fix" {
-            result = lint(".", true);
-        } else {
-            result = lint(&args[1], false);
+    if args.len() >= 2 && args.len() <= 3 && args[1] == "init" {
+        result = commands::init::run(args.get(2).map_or(".", String::as_str)).map(|_| true);
+    } else if args.len() >= 3 && args.len() <= 4 && args[1] == "config" && args[2] == "show" {
+        result = commands::config::show(args.get(3).map_or(".", String::as_str)).map(|_| true);
+    } else if args.len() == 3 && args[1] == "config" && args[2] == "schema" {
+        commands::config::schema();
+        result = Ok(true);
+    } else if args[1..]
+        .iter()
+        .all(|arg| arg == "--fix" || !arg.starts_with('-'))
+    {
+        let fix = args[1..].iter().any(|arg| arg == "--fix");
+        let mut paths: Vec<&str> = args[1..]
+            .iter()
+            .filter(|arg| *arg != "--fix")
+            .map(String::as_str)
+            .collect();
+        if paths.is_empty() {
+            paths.push(".");
         }
-    } else if args.len() == 3 && args[2] == "--fix" {
-        result = lint(&args[1], true);
+        result = lint(&paths, fix);
     } else {
-        eprintln!("Usage: gold [path] [--fix]");
+        eprintln!("Usage: gold [path ...] [--fix]");
+        eprintln!("       gold init [path]");
+        eprintln!("       gold config show [path]");
+        eprintln!("       gold config schema");
         return ExitCode::FAILURE;
     }
 
@@ -41,41 +62,100 @@ fn main() -> ExitCode {
     }
 }
 
-pub fn lint(path: &str, fix: bool) -> Result<bool, SimpleError> {
-    let mut go_mod_files = WalkDir::new(path)
-        .into_iter()
-        .filter_entry(|e| {
-            e.file_name()
-                .to_str()
-                .map(|s| {
-                    !(e.path().join("..").join("go.mod").is_file()
-                        || s.starts_with('.') && s != "." && s != "..")
-                })
-                .unwrap_or(false)
-        })
-        .filter_map(|e| e.ok())
-        .filter(|e| {
-            e.file_name()
-                .to_str()
-                .map(|s| s == "go.mod")
-                .unwrap_or(false)
-        })
-        .peekable();
+// Lints files and directories. Directories are searched for modules, including modules nested
+// in other modules. Files, and other directories, are linted with the configuration of the module
+// containing them, or with the default configuration outside any module.
+pub fn lint(paths: &[&str], fix: bool) -> Result<bool, SimpleError> {
+    let mut exit = true;
+    let mut targets: Vec<(PathBuf, Vec<PathBuf>)> = vec![];
 
-    if go_mod_files.peek().is_none() {
-        bail!("no go.mod file found in {}", path);
-    }
+    for path in paths {
+        let path = Path::new(path);
+        if !path.exists() {
+            bail!("{}: no such file or directory", path.display());
+        }
 
-    let mut exit = true;
+        // A directory of a module is linted as part of that module, which stops at the modules
+        // nested in it, so those are linted on their own.
+        let is_root = path.join("go.mod").is_file() || path.join("go.work").is_file();
+        let module = find_module(path);
+        let found = path.is_dir() && lint_modules(path, fix, &mut exit)?;
 
-    for file in go_mod_files {
-        let module_linter = ModuleLinter::new(fix);
+        if found && (is_root || module.is_none()) {
+            continue;
+        }
 
-        let mut dir = file.path().to_path_buf();
-        dir.pop();
+        match module {
+            Some(module) => {
+                let relative = normalize(path);
+                let target = module.join(relative.strip_prefix(normalize(&module)).unwrap());
+                match targets.iter_mut().find(|(m, _)| *m == module) {
+                    Some((_, paths)) => paths.push(target),
+                    None => targets.push((module, vec![target])),
+                }
+            }
+            None => exit &= lint_loose(path, fix),
+        }
+    }
 
-        exit &= module_linter.run(dir.to_str().unwrap());
+    for (module, paths) in targets {
+        let mut module_linter = ModuleLinter::new(fix);
+        if let Some(workspace) = Workspace::find(&module)? {
+            module_linter = module_linter.with_workspace(&workspace);
+        }
+        exit &= module_linter.run(&module, &paths)?;
     }
 
     Ok(exit)
 }
+
+// Lints the modules found in a directory, including modules nested in other modules. A go.work
+// file limits the modules of its directory to the ones it uses. Returns whether any module was
+// found.
+fn lint_modules(path: &Path, fix: bool, exit: &mut bool) -> Result<bool, SimpleError> {
+    let mut found = false;
+
+    let mut walk_dir = WalkDir::new(path).sort_by_file_name().into_iter();
+    while let Some(entry) = walk_dir.next() {
+        let Ok(entry) = entry else {
+            continue;
+        };
+
+        if !entry.file_type().is_dir() {
+            continue;
+        }
+
+        let hidden = entry.depth() > 0
+            && entry
+                .file_name()
+                .to_str()
+                .is_none_or(|name| name.starts_with('.'));
+        if hidden {
+            walk_dir.skip_current_dir();
+            continue;
+        }
+
+        let dir = entry.path();
+        if dir.join("go.work").is_file() {
+            walk_dir.skip_current_dir();
+
+            let workspace = Workspace::load(dir)?;
+            eprintln!("Workspace: {}", dir.join("go.work").display());
+            for module in &workspace.modules {
+                if !module.join("go.mod").is_file() {
+                    bail!("no go.mod file found in {}", module.display());
+                }
+
+                let module_linter = ModuleLinter::new(fix).with_workspace(&workspace);
+                *exit &= module_linter.run(module, slice::from_ref(module))?;
+                found = true;
+            }
+        } else if dir.join("go.mod").is_file() {
+            let module_linter = ModuleLinter::new(fix);
+            *exit &= module_linter.run(dir, &[dir.to_path_buf()])?;
+            found = true;
+        }
+    }
+
+    Ok(found)
+}
@@ -1,17 +1,38 @@
+pub mod workspace;
+
 use crate::{
-    configuration::{golangci::GolangciConfiguration, Configuration},
+    configuration::{
+        golangci::{self, GolangciConfiguration},
+        normalize, Configuration,
+    },
     file_linter::FileLinter,
 };
+use simple_error::SimpleError;
 use std::{
-    collections::HashSet,
-    fs::{self, File},
-    path::Path,
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+    rc::Rc,
 };
-use tree_sitter::{Parser, QueryCursor};
+use tree_sitter::{Parser, QueryCursor, Tree};
 use walkdir::WalkDir;
+use workspace::Workspace;
+
+// The module being linted, as declared by its go.mod file.
+#[derive(Default)]
+pub struct Module {
+    pub path: Option<String>,
+    // minor version of the go directive
+    pub go_version: Option<u32>,
+    // the directory of the go.mod file
+    pub dir: Option<PathBuf>,
+}
 
 pub struct ModuleLinter {
-    pub configuration: Configuration,
+    pub dir: PathBuf,
+    pub module: Module,
+    pub workspace: Option<PathBuf>,
+    pub configurations: HashMap<PathBuf, Rc<Configuration>>,
     pub fix: bool,
 }
 
@@ -22,74 +43,295 @@ extern "C" {
 impl ModuleLinter {
     pub fn new(fix: bool) -> Self {
         ModuleLinter {
-            configuration: Configuration::default(),
+            dir: PathBuf::new(),
+            module: Module::default(),
+            workspace: None,
+            configurations: HashMap::new(),
             fix,
         }
     }
 
-    pub fn run(mut self, dir: &str) -> bool {
-        let path = Path::new(dir);
+    // Lints the module as part of a workspace, whose configuration applies to all its modules.
+    pub fn with_workspace(mut self, workspace: &Workspace) -> Self {
+        self.workspace = Some(workspace.dir.clone());
+        self
+    }
+
+    // Lints the files and directories of the module given as targets, which are paths under the
+    // module root.
+    pub fn run(mut self, dir: &Path, targets: &[PathBuf]) -> Result<bool, SimpleError> {
+        self.dir = dir.to_path_buf();
 
-        if let Some(module) = get_module(path) {
+        self.module = get_module(dir);
+        if let Some(module) = &self.module.path {
             eprintln!("Module: {module}");
         }
 
-        if let Ok(file) = File::open(path.join(".gold.yml")) {
-            eprintln!("Configuration: .gold.yml");
-            self.configuration = serde_yaml::from_reader(&file).unwrap();
-        } else if let Ok(file) = File::open(path.join(".golangci.yml")) {
-            eprintln!("Configuration: .golangci.yml");
-            let gc: GolangciConfiguration = serde_yaml::from_reader(&file).unwrap();
-            self.configuration = Configuration::from(gc);
-        } else {
-            eprintln!("Configuration: default");
+        let configuration = load_configuration(dir, self.workspace.as_deref())?;
+        self.configurations
+            .insert(dir.to_path_buf(), Rc::new(configuration));
+
+        let mut exit = true;
+
+        for target in targets {
+            if self.is_ignored(target)? {
+                continue;
+            }
+
+            let mut walk_dir = WalkDir::new(target).sort_by_file_name().into_iter();
+            while let Some(entry) = walk_dir.next() {
+                let Ok(entry) = entry else {
+                    continue;
+                };
+
+                if entry.depth() > 0 {
+                    // Nested modules are linted on their own, with their own configuration.
+                    if entry.file_type().is_dir() && entry.path().join("go.mod").is_file() {
+                        walk_dir.skip_current_dir();
+                        continue;
+                    }
+
+                    let parent = self.configuration(entry.path().parent().unwrap())?;
+                    if parent.is_ignored(entry.path()) {
+                        if entry.file_type().is_dir() {
+                            walk_dir.skip_current_dir();
+                        }
+                        continue;
+                    }
+                }
+
+                if is_source_file(&entry) {
+                    let configuration = self.configuration(entry.path().parent().unwrap())?;
+                    let mut file_linter = FileLinter::new(
+                        entry.path().display().to_string(),
+                        self.fix,
+                        &self.module,
+                        &configuration,
+                    );
+                    exit &= file_linter.run();
+                }
+            }
         }
 
-        let mut ignore = HashSet::new();
-        if let Some(patterns) = &self.configuration.ignore {
-            for pattern in patterns {
-                ignore.insert(Path::new(dir).join(pattern));
+        Ok(exit)
+    }
+
+    // Checks whether a path inside the module, or one of its parent directories, is ignored.
+    fn is_ignored(&mut self, path: &Path) -> Result<bool, SimpleError> {
+        let mut path = path;
+        while path != self.dir {
+            let Some(parent) = path.parent() else {
+                break;
+            };
+            if self.configuration(parent)?.is_ignored(path) {
+                return Ok(true);
             }
+            path = parent;
         }
 
-        let walk_dir = WalkDir::new(dir)
-            .sort_by_file_name()
-            .into_iter()
-            .filter_entry(|entry| !ignore.contains(entry.path()))
-            .filter_map(|e| e.ok())
-            .filter(is_source_file);
+        Ok(false)
+    }
 
-        let mut exit = true;
+    // Returns the configuration for a directory inside the module, refined by the .gold.yml
+    // files between the module root and the directory.
+    fn configuration(&mut self, dir: &Path) -> Result<Rc<Configuration>, SimpleError> {
+        if let Some(configuration) = self.configurations.get(dir) {
+            return Ok(configuration.clone());
+        }
+
+        let parent = self.configuration(dir.parent().unwrap())?;
+
+        let path = dir.join(".gold.yml");
+        let configuration = if path.is_file() {
+            let mut configuration = parent.as_ref().clone();
+            for (path, layer) in Configuration::load_layers(&path)? {
+                eprintln!("Configuration: {}", display(&self.dir, &path));
+                configuration = configuration.merge(layer);
+            }
+            Rc::new(configuration)
+        } else {
+            parent
+        };
+
+        self.configurations
+            .insert(dir.to_path_buf(), configuration.clone());
+
+        Ok(configuration)
+    }
+}
+
+// Merges the configuration files found from the repository root down to the module root, so
+// that a company-wide configuration applies to every module in the repository.
+fn load_configuration(path: &Path, workspace: Option<&Path>) -> Result<Configuration, SimpleError> {
+    let layers = get_root_layers(path, workspace)?;
+
+    if layers.is_empty() {
+        eprintln!("Configuration: default");
+    }
+
+    let mut configuration = Configuration::default();
+    for (file, layer) in layers {
+        eprintln!("Configuration: {}", display(path, &file));
+        configuration = configuration.merge(layer);
+    }
+
+    Ok(configuration)
+}
+
+// Reads the configuration files that apply to a module root, from the most general to the most
+// specific, without merging them. The configuration of the workspace the module belongs to comes
+// first, unless the workspace is one of the module's parent directories anyway.
+pub fn get_root_layers(
+    path: &Path,
+    workspace: Option<&Path>,
+) -> Result<Vec<(PathBuf, Configuration)>, SimpleError> {
+    let mut layers = vec![];
+
+    let ancestors = get_ancestors(path);
+
+    if let Some(workspace) = workspace {
+        let absolute = workspace.canonicalize().ok();
+        if !ancestors
+            .iter()
+            .any(|ancestor| ancestor.canonicalize().ok() == absolute)
+        {
+            layers.extend(get_layers(workspace)?);
+        }
+    }
+
+    for dir in ancestors.iter().rev() {
+        layers.extend(get_layers(dir)?);
+    }
+
+    Ok(layers)
+}
+
+// Reads the configuration file of a directory, preferring .gold.yml to golangci-lint files.
+fn get_layers(dir: &Path) -> Result<Vec<(PathBuf, Configuration)>, SimpleError> {
+    let gold = dir.join(".gold.yml");
+    let golangci = golangci::FILENAMES
+        .iter()
+        .map(|filename| dir.join(filename))
+        .find(|path| path.is_file());
+
+    if gold.is_file() {
+        Configuration::load_layers(&gold)
+    } else if let Some(golangci) = golangci {
+        let gc = GolangciConfiguration::load(&golangci)?;
+        Ok(vec![(
+            normalize(&golangci),
+            Configuration::from(gc).relative_to(dir),
+        )])
+    } else {
+        Ok(vec![])
+    }
+}
 
-        for file in walk_dir {
+// Lints the Go files of a file or directory outside any module with the default configuration.
+pub fn lint_loose(path: &Path, fix: bool) -> bool {
+    let module = Module::default();
+    let configuration = Configuration::default();
+    let mut exit = true;
+
+    let walk_dir = WalkDir::new(path)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_entry(|e| {
+            e.depth() == 0 || e.file_name().to_str().is_some_and(|s| !s.starts_with('.'))
+        });
+    for entry in walk_dir.filter_map(|e| e.ok()) {
+        if is_source_file(&entry) {
             let mut file_linter = FileLinter::new(
-                file.path().display().to_string(),
-                self.fix,
-                &self.configuration,
+                entry.path().display().to_string(),
+                fix,
+                &module,
+                &configuration,
             );
             exit &= file_linter.run();
         }
+    }
+
+    exit
+}
+
+// Finds the root of the module containing a path, by searching upward for a go.mod file.
+pub fn find_module(path: &Path) -> Option<PathBuf> {
+    let mut dir = if path.is_file() {
+        path.parent()?.to_path_buf()
+    } else {
+        path.to_path_buf()
+    };
 
-        exit
+    for ancestor in path.canonicalize().ok()?.ancestors() {
+        if ancestor.is_dir() && ancestor.join("go.mod").is_file() {
+            let module = normalize(&dir);
+            return Some(if module.as_os_str().is_empty() {
+                PathBuf::from(".")
+            } else {
+                module
+            });
+        }
+        if ancestor.is_dir() {
+            dir.push("..");
+        }
     }
+
+    None
 }
 
-fn get_module(path: &Path) -> Option<String> {
-    let mut cursor = QueryCursor::new();
+// Lists the module root and its parent directories up to the repository root, or only the
+// module root if it is not inside a git repository.
+fn get_ancestors(path: &Path) -> Vec<PathBuf> {
+    let mut ancestors = vec![path.to_path_buf()];
 
-    let query = tree_sitter::Query::new(
-        unsafe { tree_sitter_gomod() },
-        "(module_directive (module_path) @path)",
-    )
-    .ok()?;
+    if let Ok(absolute) = path.canonicalize() {
+        let mut dir = path.to_path_buf();
+        for ancestor in absolute.ancestors() {
+            if ancestor.join(".git").exists() {
+                return ancestors;
+            }
+            dir.push("..");
+            ancestors.push(dir.clone());
+        }
+    }
+
+    vec![path.to_path_buf()]
+}
 
+fn display(root: &Path, path: &Path) -> String {
+    path.strip_prefix(root)
+        .unwrap_or(path)
+        .display()
+        .to_string()
+}
+
+fn get_module(path: &Path) -> Module {
     let mut parser = Parser::new();
-    parser.set_language(unsafe { tree_sitter_gomod() }).ok()?;
+    if parser.set_language(unsafe { tree_sitter_gomod() }).is_err() {
+        return Module::default();
+    }
 
-    let source = fs::read_to_string(path.join("go.mod")).ok()?;
-    let tree = parser.parse(&source, None)?;
+    let Ok(source) = fs::read_to_string(path.join("go.mod")) else {
+        return Module::default();
+    };
+    let Some(tree) = parser.parse(&source, None) else {
+        return Module::default();
+    };
 
-    let module = cursor
+    Module {
+        path: capture(&source, &tree, "(module_directive (module_path) @path)"),
+        go_version: capture(&source, &tree, "(go_directive (go_version) @version)")
+            .and_then(|version| minor_version(&version)),
+        dir: Some(path.to_path_buf()),
+    }
+}
+
+// Returns the text of the first capture of a query in go.mod.
+fn capture(source: &str, tree: &Tree, query: &str) -> Option<String> {
+    let mut cursor = QueryCursor::new();
+    let query = tree_sitter::Query::new(unsafe { tree_sitter_gomod() }, query).ok()?;
+
+    let text = cursor
         .matches(&query, tree.root_node(), source.as_bytes())
         .next()?
         .captures[0]
@@ -98,9 +340,31 @@ fn get_module(path: &Path) -> Option<String> {
         .ok()?
         .to_string();
 
-    Some(module)
+    Some(text)
+}
+
+// Parses the minor version of a Go version such as "1.21", "1.21.3" or "1.21rc1".
+fn minor_version(version: &str) -> Option<u32> {
+    let minor = version.strip_prefix("1.")?;
+    let end = minor
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(minor.len());
+    minor[..end].parse().ok()
 }
 
 fn is_source_file(entry: &walkdir::DirEntry) -> bool {
     entry.metadata().unwrap().is_file() && entry.path().display().to_string().ends_with(".go")
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_minor_version() {
+        assert_eq!(minor_version("1.21"), Some(21));
+        assert_eq!(minor_version("1.21.3"), Some(21));
+        assert_eq!(minor_version("1.22rc1"), Some(22));
+        assert_eq!(minor_version("2.0"), None);
+    }
+}
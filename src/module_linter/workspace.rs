@@ -0,0 +1,122 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use simple_error::SimpleError;
+
+use crate::configuration::normalize;
+
+// A Go workspace, declared by a go.work file.
+pub struct Workspace {
+    pub dir: PathBuf,
+    pub modules: Vec<PathBuf>,
+}
+
+impl Workspace {
+    // Reads the go.work file in a directory.
+    pub fn load(dir: &Path) -> Result<Self, SimpleError> {
+        let path = dir.join("go.work");
+        let source = fs::read_to_string(&path)
+            .map_err(|e| SimpleError::new(format!("{}: {e}", path.display())))?;
+
+        let modules = parse(&source)
+            .map_err(|e| SimpleError::new(format!("{}:{e}", path.display())))?
+            .iter()
+            .map(|module| normalize(&dir.join(module)))
+            .collect();
+
+        Ok(Workspace {
+            dir: dir.to_path_buf(),
+            modules,
+        })
+    }
+
+    // Finds the workspace that a module belongs to, by searching upward for a go.work file that
+    // uses the module.
+    pub fn find(module: &Path) -> Result<Option<Self>, SimpleError> {
+        let Ok(absolute) = module.canonicalize() else {
+            return Ok(None);
+        };
+
+        let mut dir = module.to_path_buf();
+        for ancestor in absolute.ancestors() {
+            if ancestor.join("go.work").is_file() {
+                let workspace = Workspace::load(&normalize(&dir))?;
+                let uses = workspace
+                    .modules
+                    .iter()
+                    .any(|m| m.canonicalize().is_ok_and(|m| m == absolute));
+                return Ok(uses.then_some(workspace));
+            }
+            dir.push("..");
+        }
+
+        Ok(None)
+    }
+}
+
+// Lists the module directories of the use directives in a go.work file, in both the single-line
+// and the block form.
+fn parse(source: &str) -> Result<Vec<String>, String> {
+    let mut modules = vec![];
+    let mut block = false;
+
+    for (i, line) in source.lines().enumerate() {
+        let line = line.split("//").next().unwrap().trim();
+
+        let path = if block {
+            if line == ")" {
+                block = false;
+                continue;
+            }
+            line
+        } else if let Some(rest) = line
+            .strip_prefix("use")
+            .filter(|rest| rest.starts_with([' ', '\t', '(']))
+        {
+            let rest = rest.trim_start();
+            if rest == "(" {
+                block = true;
+                continue;
+            }
+            rest
+        } else {
+            continue;
+        };
+
+        if path.is_empty() {
+            continue;
+        }
+
+        let path = path.trim_matches(|c| c == '"' || c == '`');
+        if path.is_empty() || path.contains(char::is_whitespace) {
+            return Err(format!("{}: invalid use directive", i + 1));
+        }
+
+        modules.push(path.to_string());
+    }
+
+    if block {
+        return Err(format!(
+            "{}: unterminated use block",
+            source.lines().count()
+        ));
+    }
+
+    Ok(modules)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_use() {
+        let source =
+            "go 1.21\n\nuse ./a\n\nuse (\n\t./b // comment\n\t\"./c\"\n)\n\nreplace x => ./x\n";
+        assert_eq!(parse(source).unwrap(), vec!["./a", "./b", "./c"]);
+
+        assert!(parse("use (\n\t./a\n").is_err());
+    }
+}
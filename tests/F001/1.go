@@ -3,3 +3,20 @@ package F001
 func f1(a, b int)                      {}
 func f2(a int, b, c string, d *string) {}
 func f3(a, b, c, d string)             {}
+
+func f4[T, U any](a, b T) (c, d U) { return }
+
+type s1[K, V comparable] struct{}
+
+func (s *s1[K, V]) f5(a, b K) {}
+
+type i1 interface {
+	f6(a, b int) (c, d string)
+}
+
+var (
+	f7 = func(a, b int) {}
+	f8 func(a, b int) (c, d string)
+)
+
+func f9(a, b func(c, d int)) {}
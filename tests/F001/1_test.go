@@ -3,3 +3,20 @@ package F001_test
 func f1(a int, b int)                         {}
 func f2(a int, b string, c string, d *string) {}
 func f3(a, b string, c, d string)             {}
+
+func f4[T any, U any](a T, b T) (c U, d U) { return }
+
+type s1[K comparable, V comparable] struct{}
+
+func (s *s1[K, V]) f5(a K, b K) {}
+
+type i1 interface {
+	f6(a int, b int) (c string, d string)
+}
+
+var (
+	f7 = func(a int, b int) {}
+	f8 func(a int, b int) (c string, d string)
+)
+
+func f9(a func(c int, d int), b func(c int, d int)) {}
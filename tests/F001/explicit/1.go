@@ -0,0 +1,5 @@
+package explicit
+
+func f1(a int, b int)                         {}
+func f2[T any, U any](a T, b T) (c U, d U)    { return }
+func f3(a int, b string, c string, d *string) {}
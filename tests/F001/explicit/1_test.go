@@ -0,0 +1,5 @@
+package explicit_test
+
+func f1(a, b int)                      {}
+func f2[T, U any](a, b T) (c, d U)     { return }
+func f3(a int, b, c string, d *string) {}
@@ -2,6 +2,7 @@ package F002
 
 import (
 	_ "fmt"
+	_ "slices"
 
 	_ "github.com/spf13/pflag"
 
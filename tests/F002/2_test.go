@@ -0,0 +1,9 @@
+package F002_test
+
+import (
+	_ "os"
+	_ "fmt"
+	_ "bufio"
+
+	_ "github.com/spf13/pflag"
+)
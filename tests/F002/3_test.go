@@ -0,0 +1,10 @@
+package F002_test
+
+import (
+	// leading comment
+	_ "os" // trailing comment
+	/* block comment */ _ "fmt"
+
+	_ "github.com/spf13/pflag"
+	// dangling comment
+)
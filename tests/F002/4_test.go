@@ -0,0 +1,10 @@
+package F002_test
+
+import _ "os" // os
+
+import (
+	_ "github.com/spf13/pflag"
+)
+
+// fmt is imported for its side effects.
+import _ "fmt"
@@ -0,0 +1,14 @@
+package F003
+
+import (
+	"fmt"
+	str "strings"
+
+	flag "github.com/spf13/pflag"
+)
+
+var (
+	_ = fmt.Println
+	_ = str.ToUpper
+	_ = flag.Parse
+)
@@ -0,0 +1,18 @@
+package F003_test
+
+import (
+	"fmt"
+	"fmt"
+	os "os"
+	_ "strings"
+	"strings"
+
+	pflag "github.com/spf13/pflag"
+)
+
+var (
+	_ = fmt.Println
+	_ = os.Exit
+	_ = strings.ToUpper
+	_ = pflag.Parse
+)
@@ -0,0 +1,21 @@
+package F004
+
+import (
+	_ "embed"
+	"fmt"
+	. "math"
+	str "strings"
+
+	flag "github.com/spf13/pflag"
+	"gopkg.in/yaml.v3"
+	"k8s.io/api/core/v1"
+)
+
+var (
+	_ fmt.Stringer
+	_ = str.ToUpper
+	_ = flag.Parse
+	_ = yaml.Marshal
+	_ = Pi
+	_ = v1.Pod{}
+)
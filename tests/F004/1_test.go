@@ -0,0 +1,11 @@
+package F004_test
+
+import (
+	"fmt"
+	"os"
+	str "strings"
+
+	"github.com/spf13/pflag"
+)
+
+var _ = os.Args
@@ -0,0 +1,22 @@
+package F005
+
+type s1 struct {
+	a, b int
+	c    string
+
+	d int
+	// comment
+	e int
+	f int `json:"f"`
+	g int
+	h int // h doc
+	i int // i doc
+	j int
+}
+
+var (
+	h, i int
+	j    = 1
+	k    int = 2
+	l    string
+)
@@ -0,0 +1,15 @@
+package F005_test
+
+type s1 struct {
+	a int
+	b int
+	c string
+	d string
+	e string
+}
+
+var (
+	f int
+	g int
+	h string
+)
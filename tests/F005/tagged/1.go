@@ -0,0 +1,10 @@
+package tagged
+
+type s1 struct {
+	a int `json:"a"`
+	b int `json:"b"`
+}
+
+type s2 struct {
+	a, b int
+}
@@ -0,0 +1,6 @@
+package tagged_test
+
+type s1 struct {
+	a, b int `json:"-"`
+	c    string
+}
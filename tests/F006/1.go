@@ -0,0 +1,28 @@
+package F006
+
+import (
+	"bufio"
+	"fmt"
+)
+
+// The declarations of a file are ordered by kind.
+
+const c1 = 1
+
+var v1 = 2
+
+// t1 is a type.
+type t1 struct{}
+
+// newT1 creates a t1.
+func newT1() *t1 { return &t1{} }
+
+func (t *t1) m1() {}
+
+type t2 int
+
+func (t t2) String() string { return fmt.Sprint(int(t)) }
+
+func NewReader() *bufio.Reader { return nil }
+
+func f1() {}
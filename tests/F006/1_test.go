@@ -0,0 +1,16 @@
+package F006_test
+
+func f1() {}
+
+// t1 is a type.
+type t1 struct{}
+
+func (t t2) m2() {} // m2 is a method.
+
+type t2 int
+
+const c1 = 1
+
+func newT1() *t1 { return &t1{} }
+
+var v1 = 2
@@ -0,0 +1,10 @@
+package F006_test
+
+func a() {} // a
+
+// B is a type.
+type B int
+
+var v = 1
+
+const c = 1
@@ -0,0 +1,15 @@
+package F007
+
+const MaxSize = 1
+
+type HTTPServer struct {
+	userID int
+	URL    string
+}
+
+func (s *HTTPServer) serveJSON(apiKey string) {}
+
+func parseURL(rawURL string) (id int) {
+	n := len(rawURL)
+	return n
+}
@@ -0,0 +1,22 @@
+package F007_test
+
+import "testing"
+
+const MAX_SIZE = 1
+
+type HttpServer struct {
+	user_id int
+	Url     string
+}
+
+func (s *HttpServer) serveJson(api_key string) {}
+
+func parseUrl(rawUrl string) (Id int) {
+	n_bytes := len(rawUrl)
+	sizes := map[int]int{n_bytes: 1}
+	return sizes[n_bytes]
+}
+
+func Test_parseUrl(t *testing.T) {
+	_ = parseUrl("")
+}
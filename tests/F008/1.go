@@ -0,0 +1,23 @@
+package F008
+
+type Client struct {
+	name string
+}
+
+func NewClient(name string) *Client {
+	return &Client{name: name}
+}
+
+func (c *Client) Name() string {
+	return c.name
+}
+
+func (c *Client) Get(key string) string {
+	return key
+}
+
+func (c *Client) Getter() string {
+	return c.name
+}
+
+func (c *Client) GetReady() {}
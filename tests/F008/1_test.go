@@ -0,0 +1,19 @@
+package F008
+
+const F008Version = 1
+
+type F008Client struct {
+	name string
+}
+
+func NewF008Client(name string) *F008Client {
+	return &F008Client{name: name}
+}
+
+func (c *F008Client) GetName() string {
+	return c.name
+}
+
+func GetDefault() *F008Client {
+	return nil
+}
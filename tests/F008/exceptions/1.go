@@ -0,0 +1,9 @@
+package F008
+
+type F008Client struct {
+	name string
+}
+
+func (c *F008Client) GetName() string {
+	return c.name
+}
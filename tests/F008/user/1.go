@@ -0,0 +1,9 @@
+package user
+
+type UserService struct{}
+
+type Service struct{}
+
+func (s *Service) GetUser() (string, error) {
+	return "", nil
+}
@@ -0,0 +1,3 @@
+package generated
+
+func f1(a string, b string) {}
@@ -0,0 +1,17 @@
+package gci
+
+import (
+	"fmt"
+
+	"github.com/spf13/pflag"
+
+	_ "embed"
+
+	str "strings"
+)
+
+var (
+	_ = fmt.Println
+	_ = pflag.Parse
+	_ = str.ToUpper
+)
@@ -0,0 +1,14 @@
+package gci_test
+
+import (
+	"fmt"
+
+	str "strings"
+
+	_ "embed"
+)
+
+var (
+	_ = fmt.Println
+	_ = str.ToUpper
+)
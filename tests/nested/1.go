@@ -0,0 +1,9 @@
+package nested
+
+import (
+	_ "github.com/spf13/pflag"
+
+	_ "fmt"
+)
+
+func f1(a int, b int) {}
@@ -0,0 +1,3 @@
+module github.com/brianstrauch/gold/tests/nested
+
+go 1.19
@@ -0,0 +1,3 @@
+package sub
+
+func f1(a int, b int) {}
@@ -0,0 +1,15 @@
+//nolint:gci // imports are grouped by hand
+package nolint
+
+import (
+	_ "github.com/spf13/pflag"
+
+	_ "fmt"
+)
+
+//nolint:gofumpt
+func f1(a int, b int) {}
+
+func f2(a int, b int) {} //nolint:F001
+
+func f3(a int, b int) {} //nolint
@@ -0,0 +1,6 @@
+package nolint_test
+
+//nolint:gci
+func f1(a int, b int) {}
+
+func f2(a int, b int) {} //nolint:errcheck,gci
@@ -0,0 +1,10 @@
+package outer
+
+import (
+	"os"
+	"fmt"
+)
+
+func f() {
+	fmt.Fprintln(os.Stdout)
+}
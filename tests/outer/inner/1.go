@@ -0,0 +1,10 @@
+package inner
+
+import (
+	"os"
+	"fmt"
+)
+
+func f() {
+	fmt.Fprintln(os.Stdout)
+}
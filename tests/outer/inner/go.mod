@@ -0,0 +1,3 @@
+module github.com/brianstrauch/gold/tests/outer/inner
+
+go 1.19
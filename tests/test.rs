@@ -2,7 +2,8 @@
 extern crate duct;
 
 use pretty_assertions::assert_eq;
-use std::fs;
+use std::{fs, path::Path};
+use walkdir::WalkDir;
 
 #[test]
 fn test() {
@@ -20,3 +21,120 @@ fn test() {
 
     assert_eq!(output.status.success(), false);
 }
+
+#[test]
+fn config_show() {
+    let output = cmd!(
+        "cargo",
+        "run",
+        "--quiet",
+        "config",
+        "show",
+        "tests/config/1.go"
+    )
+    .stdout_capture()
+    .run()
+    .unwrap();
+
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        fs::read_to_string("tests/config_show.golden").unwrap()
+    );
+}
+
+#[test]
+fn config_schema() {
+    let output = cmd!("cargo", "run", "--quiet", "config", "schema")
+        .stdout_capture()
+        .run()
+        .unwrap();
+
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        fs::read_to_string("schema.json").unwrap()
+    );
+}
+
+#[test]
+fn workspace() {
+    let output = cmd!("cargo", "run", "--quiet", "tests/workspace")
+        .unchecked()
+        .stderr_to_stdout()
+        .stdout_capture()
+        .run()
+        .unwrap();
+
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        fs::read_to_string("tests/workspace.golden").unwrap()
+    );
+}
+
+#[test]
+fn files() {
+    let output = cmd!(
+        "cargo",
+        "run",
+        "--quiet",
+        "tests/F001/1_test.go",
+        "tests/ignore/1.go",
+        "tests/nested/1.go",
+        "tests/outer"
+    )
+    .unchecked()
+    .stderr_to_stdout()
+    .stdout_capture()
+    .run()
+    .unwrap();
+
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        fs::read_to_string("tests/files.golden").unwrap()
+    );
+}
+
+#[test]
+fn fix() {
+    // The fixtures are fixed in a copy, and the files that change are compared with their fixes.
+    let dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join("tests");
+    if dir.exists() {
+        fs::remove_dir_all(&dir).unwrap();
+    }
+    copy(Path::new("tests"), &dir);
+
+    cmd!("cargo", "run", "--quiet", &dir, "--fix")
+        .unchecked()
+        .stderr_capture()
+        .stdout_capture()
+        .run()
+        .unwrap();
+
+    let mut fixed = String::new();
+    for entry in WalkDir::new(&dir).sort_by_file_name() {
+        let entry = entry.unwrap();
+        let path = entry.path().strip_prefix(&dir).unwrap();
+        if !path.to_string_lossy().ends_with(".go") {
+            continue;
+        }
+
+        let source = fs::read_to_string(entry.path()).unwrap();
+        if source != fs::read_to_string(Path::new("tests").join(path)).unwrap() {
+            fixed.push_str(&format!("==> {} <==\n{source}\n", path.display()));
+        }
+    }
+    fs::remove_dir_all(&dir).unwrap();
+
+    assert_eq!(fixed, fs::read_to_string("tests/fix.golden").unwrap());
+}
+
+fn copy(from: &Path, to: &Path) {
+    for entry in WalkDir::new(from) {
+        let entry = entry.unwrap();
+        let target = to.join(entry.path().strip_prefix(from).unwrap());
+        if entry.file_type().is_dir() {
+            fs::create_dir_all(target).unwrap();
+        } else {
+            fs::copy(entry.path(), target).unwrap();
+        }
+    }
+}
@@ -0,0 +1,3 @@
+module github.com/brianstrauch/gold/tests/workspace/a
+
+go 1.19
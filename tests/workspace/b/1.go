@@ -0,0 +1,3 @@
+package b
+
+func f1(a int, b int) {}
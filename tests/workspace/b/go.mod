@@ -0,0 +1,3 @@
+module github.com/brianstrauch/gold/tests/workspace/b
+
+go 1.19